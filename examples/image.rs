@@ -1,5 +1,7 @@
 //! This example acquires raw image frames and displays them in a window.
 
+use std::time::Duration;
+
 use leapcpp::{ManagedController, Policy};
 use macroquad::prelude::*;
 
@@ -9,7 +11,10 @@ async fn main() {
 
     println!("waiting for controller to connect");
     controller.wait_until_device_connected();
-    controller.set_policy(Policy::Images);
+    if let Err(e) = controller.set_policy_and_wait(Policy::Images, Duration::from_secs(1)) {
+        eprintln!("failed to enable images: {}", e);
+        return;
+    }
     println!("starting");
 
     let mut image = Image::empty();
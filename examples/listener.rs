@@ -33,8 +33,10 @@ impl Listener for MyListener {
 
     fn on_frame(&mut self, controller: &ControllerRef) {
         println!("on_frame");
-        let id = controller.frame().id();
-        println!("- frame ID: {}", id);
+        if let Some(frame) = controller.frame() {
+            println!("- frame ID: {}", frame.id());
+            println!("- {} hands", frame.hands().len());
+        }
     }
 
     fn on_focus_gained(&mut self, controller: &ControllerRef) {
@@ -55,6 +57,9 @@ impl Listener for MyListener {
 
     fn on_device_change(&mut self, controller: &ControllerRef) {
         println!("on_device_change");
+        for device in controller.devices().iter() {
+            println!("- {:?} {}", device.device_type(), device.serial_number());
+        }
     }
 
     fn on_images(&mut self, controller: &ControllerRef) {
@@ -83,9 +88,14 @@ fn main() {
 
     let mut controller = ManagedController::new();
     controller.wait_until_device_connected();
-    controller.set_policy(Policy::Images);
+    if let Err(e) = controller.set_policy(Policy::Images) {
+        eprintln!("failed to enable images: {}", e);
+        return;
+    }
 
-    controller.add_listener(MyListener { exit: exit.clone() });
+    controller
+        .add_listener(MyListener { exit: exit.clone() })
+        .expect("failed to add listener");
 
     println!("waiting for `on_image` event");
     while !exit.load(Ordering::Relaxed) {
@@ -0,0 +1,69 @@
+//! Records a tracking session to a file, or replays a previously recorded one.
+//!
+//! Usage: `recording record <file>` or `recording replay <file>`.
+
+use std::{
+    env,
+    fs::File,
+    io::{BufReader, BufWriter},
+    process, thread,
+    time::Duration,
+};
+
+use leapcpp::{
+    recording::{self, Reader, Recorder, Speed},
+    ControllerRef, Listener, ManagedController, Policy,
+};
+
+struct PrintListener;
+
+impl Listener for PrintListener {
+    fn on_connect(&mut self, _: &ControllerRef) {
+        println!("on_connect");
+    }
+
+    fn on_disconnect(&mut self, _: &ControllerRef) {
+        println!("on_disconnect");
+    }
+
+    fn on_frame(&mut self, controller: &ControllerRef) {
+        if let Some(frame) = controller.frame() {
+            println!("frame {}: {} hands", frame.id(), frame.hands().len());
+        }
+    }
+
+    fn on_images(&mut self, controller: &ControllerRef) {
+        println!("{} images", controller.images().len());
+    }
+}
+
+fn main() -> std::io::Result<()> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    match args.iter().map(|s| &**s).collect::<Vec<_>>()[..] {
+        ["record", path] => {
+            let recorder = Recorder::new(BufWriter::new(File::create(path)?))?;
+
+            let mut controller = ManagedController::new();
+            controller.wait_until_device_connected();
+            if let Err(e) = controller.set_policy(Policy::Images) {
+                eprintln!("failed to enable images, recording frames only: {}", e);
+            }
+            controller
+                .add_listener(recorder)
+                .expect("failed to add recorder");
+
+            println!("recording for 10 seconds");
+            thread::sleep(Duration::from_secs(10));
+        }
+        ["replay", path] => {
+            let reader = Reader::new(BufReader::new(File::open(path)?))?;
+            recording::replay(reader, Speed::Original, &mut PrintListener)?;
+        }
+        _ => {
+            eprintln!("usage: recording (record|replay) <file>");
+            process::exit(1);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+//! The implementations behind a [`ControllerRef`][crate::ControllerRef].
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use crate::{data::FrameData, image::ImageBuf, sys, Frame};
+
+/// The number of frames kept by the SDK, and by [`VirtualState`].
+pub(crate) const HISTORY_LEN: usize = 60;
+
+pub(crate) enum Backend {
+    /// A `Leap::Controller` talking to leapd.
+    Leap(*const sys::Leap_Controller),
+    /// Controller state maintained by this library, eg. while replaying a recording.
+    Virtual(Arc<VirtualState>),
+}
+
+/// The state of a controller that is not backed by leapd.
+#[derive(Default)]
+pub(crate) struct VirtualState {
+    inner: Mutex<VirtualInner>,
+}
+
+impl VirtualState {
+    pub(crate) fn lock(&self) -> MutexGuard<'_, VirtualInner> {
+        self.inner.lock().unwrap()
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct VirtualInner {
+    pub(crate) service_connected: bool,
+    pub(crate) connected: bool,
+    pub(crate) has_focus: bool,
+    /// Bit set of enabled `Policy` flags.
+    pub(crate) policies: u32,
+    /// Bit set of enabled `GestureType`s.
+    pub(crate) gestures: u32,
+    /// The current time, in microseconds.
+    pub(crate) now: i64,
+    /// Frames, most recent first. `None` for frames whose data could not be decoded.
+    frames: VecDeque<Option<StoredFrame>>,
+    pub(crate) images: Vec<Arc<ImageBuf>>,
+}
+
+impl VirtualInner {
+    /// Makes `frame` the most recent frame, dropping the oldest one if the history is full.
+    ///
+    /// Frames are decoded once, when they are pushed, so that the SDK sees them in order.
+    pub(crate) fn push_frame(&mut self, frame: Option<Frame>) {
+        self.push(frame.map(|frame| StoredFrame::Sdk(SdkFrame(frame))));
+    }
+
+    /// Makes `data` the most recent frame, dropping the oldest one if the history is full.
+    pub(crate) fn push_frame_data(&mut self, data: FrameData) {
+        self.push(Some(StoredFrame::Data(data)));
+    }
+
+    fn push(&mut self, frame: Option<StoredFrame>) {
+        self.frames.push_front(frame);
+        self.frames.truncate(HISTORY_LEN);
+    }
+
+    /// Returns the frame `history` frames back, unless it was pushed as [`FrameData`].
+    pub(crate) fn frame(&self, history: usize) -> Option<Frame> {
+        match self.frames.get(history)?.as_ref()? {
+            StoredFrame::Sdk(frame) => Some(frame.0.copy()),
+            StoredFrame::Data(_) => None,
+        }
+    }
+
+    /// Returns a copy of the frame `history` frames back.
+    ///
+    /// The motion factors of SDK frames are computed relative to the frame before them, if that is
+    /// an SDK frame as well.
+    pub(crate) fn frame_data(&self, history: usize) -> Option<FrameData> {
+        match self.frames.get(history)?.as_ref()? {
+            StoredFrame::Sdk(frame) => {
+                let since = self.frame(history + 1);
+                Some(FrameData::from_frames(&frame.0, since.as_ref()))
+            }
+            StoredFrame::Data(data) => Some(data.clone()),
+        }
+    }
+}
+
+/// A frame kept in the history of a [`VirtualState`].
+enum StoredFrame {
+    Sdk(SdkFrame),
+    Data(FrameData),
+}
+
+/// A [`Frame`] that can be stored in a [`VirtualState`].
+struct SdkFrame(Frame);
+
+// SAFETY: SDK frames are immutable and reference-counted, and the SDK itself hands out copies of
+// the same frame to several threads. The stored frame is only accessed while the state is locked.
+unsafe impl Send for SdkFrame {}
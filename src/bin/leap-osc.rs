@@ -0,0 +1,80 @@
+//! Sends hand and gesture data as OSC messages over UDP.
+//!
+//! Usage: `leap-osc [options] [host:port]`, where the target defaults to `127.0.0.1:9000`.
+//!
+//! Options:
+//!
+//! - `--palm <template>`, `--tip <template>`, `--pinch <template>`, `--grab <template>`,
+//!   `--gesture <template>`: set the OSC address template of the corresponding messages, or
+//!   disable them with `none`. See `leapcpp::osc::BridgeConfig` for the placeholders.
+//! - `--normalize`: send positions normalized to the interaction box instead of in millimeters.
+
+use std::{env, process, thread};
+
+use leapcpp::{
+    osc::{Bridge, BridgeConfig},
+    ManagedController, Policy,
+};
+
+const USAGE: &str = "usage: leap-osc [--palm|--tip|--pinch|--grab|--gesture <template>|none]... \
+                     [--normalize] [host:port]";
+
+fn main() {
+    let mut config = BridgeConfig::default();
+    let mut target = "127.0.0.1:9000".to_string();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let field = match &*arg {
+            "--palm" => &mut config.palm,
+            "--tip" => &mut config.finger_tip,
+            "--pinch" => &mut config.pinch,
+            "--grab" => &mut config.grab,
+            "--gesture" => &mut config.gesture,
+            "--normalize" => {
+                config.normalize = true;
+                continue;
+            }
+            _ if !arg.starts_with("--") => {
+                target = arg;
+                continue;
+            }
+            _ => usage(),
+        };
+        *field = match args.next() {
+            Some(template) if template == "none" => None,
+            Some(template) => Some(template),
+            None => usage(),
+        };
+    }
+
+    let bridge = match Bridge::new(&*target, config) {
+        Ok(bridge) => bridge,
+        Err(e) => {
+            eprintln!("invalid target {}: {}", target, e);
+            process::exit(1);
+        }
+    };
+    println!("sending OSC to {}", target);
+
+    let mut controller = ManagedController::new();
+    if let Err(e) = controller.add_listener(bridge) {
+        eprintln!("failed to add listener: {}", e);
+        process::exit(1);
+    }
+
+    // This process has no window, so it never has focus.
+    controller.wait_until_service_connected();
+    if let Err(e) = controller.set_policy(Policy::BackgroundFrames) {
+        eprintln!("failed to enable background frames: {}", e);
+    }
+
+    loop {
+        thread::park();
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(1);
+}
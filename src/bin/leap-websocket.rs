@@ -0,0 +1,47 @@
+//! Serves tracking data to leapjs and other clients of the Leap Motion WebSocket protocol.
+//!
+//! Usage: `leap-websocket [address]`, where the address defaults to `127.0.0.1:6437`.
+
+use std::{env, process, thread};
+
+use leapcpp::{
+    websocket::{self, Server},
+    ManagedController, Policy,
+};
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let addr = match &args[..] {
+        [] => format!("127.0.0.1:{}", websocket::DEFAULT_PORT),
+        [addr] => addr.clone(),
+        _ => {
+            eprintln!("usage: leap-websocket [address]");
+            process::exit(1);
+        }
+    };
+
+    let server = match Server::bind(&addr) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("failed to listen on {}: {}", addr, e);
+            process::exit(1);
+        }
+    };
+    println!("listening on ws://{}", server.local_addr());
+
+    let mut controller = ManagedController::new();
+    if let Err(e) = controller.add_listener(server) {
+        eprintln!("failed to add listener: {}", e);
+        process::exit(1);
+    }
+
+    // This process has no window, so it never has focus; the clients report their own focus.
+    controller.wait_until_service_connected();
+    if let Err(e) = controller.set_policy(Policy::BackgroundFrames) {
+        eprintln!("failed to enable background frames: {}", e);
+    }
+
+    loop {
+        thread::park();
+    }
+}
@@ -0,0 +1,199 @@
+//! Access to the configuration of the Leap Motion service.
+
+use std::ffi::{CStr, CString};
+
+use crate::{ffi, sys};
+
+/// The configuration of the Leap Motion service, obtained from
+/// [`ControllerRef::config`][crate::ControllerRef::config].
+///
+/// Keys are strings like `"Gesture.Circle.MinRadius"`. Values can only be read and changed while
+/// the controller is connected to the service. Changes are not applied until [`Config::save`] is
+/// called.
+///
+/// All methods taking a key panic if it contains a NUL byte.
+pub struct Config {
+    inner: Box<sys::Leap_Config>,
+}
+
+impl Config {
+    pub(crate) fn from_raw(inner: Box<sys::Leap_Config>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the type of the value stored under `key`.
+    ///
+    /// Returns `None` if there is no such key, or if the SDK reports a type this library does not
+    /// know.
+    pub fn value_type(&self, key: &str) -> Option<ValueType> {
+        let key = c_key(key);
+        ValueType::from_raw(unsafe { ffi::Leap_Config_typeCString(&*self.inner, key.as_ptr()) })
+    }
+
+    /// Returns the boolean value stored under `key`.
+    pub fn get_bool(&self, key: &str) -> bool {
+        let key = c_key(key);
+        unsafe { ffi::Leap_Config_getBoolCString(&*self.inner, key.as_ptr()) }
+    }
+
+    /// Changes the boolean value stored under `key`.
+    ///
+    /// Returns whether the value was changed successfully.
+    pub fn set_bool(&mut self, key: &str, value: bool) -> bool {
+        let key = c_key(key);
+        unsafe { ffi::Leap_Config_setBoolCString(&mut *self.inner, key.as_ptr(), value) }
+    }
+
+    /// Returns the integer value stored under `key`.
+    pub fn get_i32(&self, key: &str) -> i32 {
+        let key = c_key(key);
+        unsafe { ffi::Leap_Config_getInt32CString(&*self.inner, key.as_ptr()) }
+    }
+
+    /// Changes the integer value stored under `key`.
+    ///
+    /// Returns whether the value was changed successfully.
+    pub fn set_i32(&mut self, key: &str, value: i32) -> bool {
+        let key = c_key(key);
+        unsafe { ffi::Leap_Config_setInt32CString(&mut *self.inner, key.as_ptr(), value) }
+    }
+
+    /// Returns the floating-point value stored under `key`.
+    pub fn get_f32(&self, key: &str) -> f32 {
+        let key = c_key(key);
+        unsafe { ffi::Leap_Config_getFloatCString(&*self.inner, key.as_ptr()) }
+    }
+
+    /// Changes the floating-point value stored under `key`.
+    ///
+    /// Returns whether the value was changed successfully.
+    pub fn set_f32(&mut self, key: &str, value: f32) -> bool {
+        let key = c_key(key);
+        unsafe { ffi::Leap_Config_setFloatCString(&mut *self.inner, key.as_ptr(), value) }
+    }
+
+    /// Returns the string value stored under `key`.
+    pub fn get_string(&self, key: &str) -> String {
+        let key = c_key(key);
+        unsafe {
+            let raw = ffi::Leap_Config_getStringCString(&*self.inner, key.as_ptr());
+            let value = CStr::from_ptr(raw).to_string_lossy().into_owned();
+            sys::Leap_Interface_deleteCString(raw);
+            value
+        }
+    }
+
+    /// Changes the string value stored under `key`.
+    ///
+    /// Returns whether the value was changed successfully.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` contains a NUL byte.
+    pub fn set_string(&mut self, key: &str, value: &str) -> bool {
+        let key = c_key(key);
+        let value = CString::new(value).expect("config value must not contain NUL bytes");
+        unsafe { ffi::Leap_Config_setStringCString(&mut *self.inner, key.as_ptr(), value.as_ptr()) }
+    }
+
+    /// Returns the current value of a gesture recognition parameter.
+    pub fn gesture_setting(&self, setting: GestureSetting) -> f32 {
+        self.get_f32(setting.key())
+    }
+
+    /// Changes a gesture recognition parameter.
+    ///
+    /// Returns whether the value was changed successfully.
+    pub fn set_gesture_setting(&mut self, setting: GestureSetting, value: f32) -> bool {
+        self.set_f32(setting.key(), value)
+    }
+
+    /// Saves the changed configuration values, and applies them to the service.
+    ///
+    /// Returns whether the configuration was saved successfully.
+    pub fn save(&mut self) -> bool {
+        unsafe { sys::Leap_Config_save(&mut *self.inner) }
+    }
+}
+
+impl Drop for Config {
+    fn drop(&mut self) {
+        // No `Config` destructor, call superclass dtor instead.
+        unsafe {
+            sys::Leap_Interface_Interface_destructor((&mut *self.inner) as *mut _ as _);
+        }
+    }
+}
+
+fn c_key(key: &str) -> CString {
+    CString::new(key).expect("config key must not contain NUL bytes")
+}
+
+/// The type of a configuration value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ValueType {
+    Bool = sys::Leap_Config_ValueType_TYPE_BOOLEAN,
+    I32 = sys::Leap_Config_ValueType_TYPE_INT32,
+    F32 = sys::Leap_Config_ValueType_TYPE_FLOAT,
+    String = sys::Leap_Config_ValueType_TYPE_STRING,
+}
+
+impl ValueType {
+    fn from_raw(raw: sys::Leap_Config_ValueType) -> Option<Self> {
+        match raw {
+            sys::Leap_Config_ValueType_TYPE_BOOLEAN => Some(Self::Bool),
+            sys::Leap_Config_ValueType_TYPE_INT32 => Some(Self::I32),
+            sys::Leap_Config_ValueType_TYPE_FLOAT => Some(Self::F32),
+            sys::Leap_Config_ValueType_TYPE_STRING => Some(Self::String),
+            _ => None,
+        }
+    }
+}
+
+/// A tuning parameter of the gestures built into the service.
+///
+/// See [`GestureType`][crate::GestureType] for the gestures themselves, which have to be enabled
+/// with [`ControllerRef::enable_gesture`][crate::ControllerRef::enable_gesture].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GestureSetting {
+    /// The minimum radius of a circle gesture, in millimeters (default: 5).
+    CircleMinRadius,
+    /// The minimum arc a circle gesture has to trace, in radians (default: 1.5π).
+    CircleMinArc,
+    /// The minimum length of a swipe gesture, in millimeters (default: 150).
+    SwipeMinLength,
+    /// The minimum velocity of a swipe gesture, in millimeters per second (default: 1000).
+    SwipeMinVelocity,
+    /// The minimum downwards velocity of a key tap, in millimeters per second (default: 50).
+    KeyTapMinDownVelocity,
+    /// The duration of the history used to detect key taps, in seconds (default: 0.1).
+    KeyTapHistorySeconds,
+    /// The minimum distance a finger has to move for a key tap, in millimeters (default: 3).
+    KeyTapMinDistance,
+    /// The minimum forward velocity of a screen tap, in millimeters per second (default: 50).
+    ScreenTapMinForwardVelocity,
+    /// The duration of the history used to detect screen taps, in seconds (default: 0.1).
+    ScreenTapHistorySeconds,
+    /// The minimum distance a finger has to move for a screen tap, in millimeters (default: 5).
+    ScreenTapMinDistance,
+}
+
+impl GestureSetting {
+    /// Returns the configuration key of this setting.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::CircleMinRadius => "Gesture.Circle.MinRadius",
+            Self::CircleMinArc => "Gesture.Circle.MinArc",
+            Self::SwipeMinLength => "Gesture.Swipe.MinLength",
+            Self::SwipeMinVelocity => "Gesture.Swipe.MinVelocity",
+            Self::KeyTapMinDownVelocity => "Gesture.KeyTap.MinDownVelocity",
+            Self::KeyTapHistorySeconds => "Gesture.KeyTap.HistorySeconds",
+            Self::KeyTapMinDistance => "Gesture.KeyTap.MinDistance",
+            Self::ScreenTapMinForwardVelocity => "Gesture.ScreenTap.MinForwardVelocity",
+            Self::ScreenTapHistorySeconds => "Gesture.ScreenTap.HistorySeconds",
+            Self::ScreenTapMinDistance => "Gesture.ScreenTap.MinDistance",
+        }
+    }
+}
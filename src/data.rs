@@ -0,0 +1,659 @@
+//! Owned snapshots of tracking data.
+//!
+//! The types in this module are plain Rust data that does not reference the SDK, so unlike
+//! [`Frame`] and the objects obtained from it, they can be sent across threads, stored, compared,
+//! and constructed from scratch (eg. in tests). Use [`Frame::to_data`] to create a [`FrameData`].
+
+use std::{error::Error, fmt, time::Duration};
+
+use crate::{
+    gesture::Gesture,
+    hand::{Arm, Hand},
+    pointable::{Bone, BoneType, Finger, FingerType, Pointable, TouchZone},
+    Frame, GestureState, GestureType, InteractionBox, Matrix, Timestamp, Vector,
+};
+
+/// An owned copy of a [`Frame`].
+///
+/// Fingers and gestures whose type (or bone type) is not known to this library are left out, and a
+/// warning is logged for each of them.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameData {
+    pub id: i64,
+    pub timestamp: Timestamp,
+    pub frames_per_second: f32,
+    pub hands: Vec<HandData>,
+    /// All fingers and tools in the frame.
+    pub pointables: Vec<PointableData>,
+    pub gestures: Vec<GestureData>,
+    /// The interaction box, or an [empty][InteractionBoxData::is_empty] one if the frame has none.
+    pub interaction_box: InteractionBoxData,
+    /// The motion of all hands and fingers since the previous frame, if that frame is known.
+    pub motion: Option<MotionData>,
+}
+
+impl FrameData {
+    /// Returns the hand with the given ID, if it is part of this frame.
+    pub fn hand(&self, id: i32) -> Option<&HandData> {
+        self.hands.iter().find(|hand| hand.id == id)
+    }
+
+    /// Returns the finger or tool with the given ID, if it is part of this frame.
+    pub fn pointable(&self, id: i32) -> Option<&PointableData> {
+        self.pointables.iter().find(|pointable| pointable.id == id)
+    }
+}
+
+impl FrameData {
+    /// Copies `frame`, along with its motion factors relative to `since`.
+    pub(crate) fn from_frames(frame: &Frame, since: Option<&Frame>) -> Self {
+        let hand_data = |hand: Hand| {
+            let mut data = HandData::from(&hand);
+            // The SDK reports no motion for hands that are not part of `since`.
+            data.motion = since
+                .filter(|since| since.hand(hand.id()).is_some())
+                .map(|since| MotionData {
+                    translation: hand.translation(since),
+                    rotation: hand.rotation_matrix(since),
+                    scale_factor: hand.scale_factor(since),
+                });
+            data
+        };
+
+        Self {
+            id: frame.id(),
+            timestamp: frame.timestamp(),
+            frames_per_second: frame.frames_per_second(),
+            hands: frame.hands().iter().map(hand_data).collect(),
+            pointables: frame.pointables().iter().map(|p| (&p).into()).collect(),
+            gestures: frame
+                .gestures()
+                .iter()
+                .filter_map(|g| {
+                    (&g).try_into()
+                        .map_err(|e| log::warn!("dropping gesture {}: {}", g.id(), e))
+                        .ok()
+                })
+                .collect(),
+            interaction_box: frame
+                .interaction_box()
+                .map(|interaction_box| (&interaction_box).into())
+                .unwrap_or_default(),
+            motion: since.map(|since| MotionData {
+                translation: frame.translation(since),
+                rotation: frame.rotation_matrix(since),
+                scale_factor: frame.scale_factor(since),
+            }),
+        }
+    }
+}
+
+impl From<&Frame> for FrameData {
+    fn from(frame: &Frame) -> Self {
+        Self::from_frames(frame, None)
+    }
+}
+
+/// The motion factors of a frame or hand, relative to an earlier frame.
+///
+/// See [`Frame::translation`], [`Frame::rotation_matrix`] and [`Frame::scale_factor`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MotionData {
+    /// The change of position, in millimeters.
+    pub translation: Vector,
+    pub rotation: Matrix,
+    /// Values above 1 indicate expansion, values below 1 contraction.
+    pub scale_factor: f32,
+}
+
+/// An owned copy of a [`Hand`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HandData {
+    pub id: i32,
+    pub is_left: bool,
+    pub palm_position: Vector,
+    pub stabilized_palm_position: Vector,
+    pub palm_velocity: Vector,
+    pub palm_normal: Vector,
+    pub palm_width: f32,
+    pub direction: Vector,
+    pub basis: Matrix,
+    pub wrist_position: Vector,
+    pub sphere_center: Vector,
+    pub sphere_radius: f32,
+    pub pinch_strength: f32,
+    pub grab_strength: f32,
+    pub confidence: f32,
+    pub time_visible: Duration,
+    /// The forearm, if the service reports one.
+    pub arm: Option<ArmData>,
+    pub fingers: Vec<FingerData>,
+    /// The motion of this hand since the previous frame, if the hand was part of that frame.
+    pub motion: Option<MotionData>,
+}
+
+impl HandData {
+    /// Returns the finger of the given type, if it is tracked.
+    pub fn finger(&self, finger_type: FingerType) -> Option<&FingerData> {
+        self.fingers
+            .iter()
+            .find(|finger| finger.finger_type == finger_type)
+    }
+}
+
+impl From<&Hand> for HandData {
+    fn from(hand: &Hand) -> Self {
+        Self {
+            id: hand.id(),
+            is_left: hand.is_left(),
+            palm_position: hand.palm_position(),
+            stabilized_palm_position: hand.stabilized_palm_position(),
+            palm_velocity: hand.palm_velocity(),
+            palm_normal: hand.palm_normal(),
+            palm_width: hand.palm_width(),
+            direction: hand.direction(),
+            basis: hand.basis(),
+            wrist_position: hand.wrist_position(),
+            sphere_center: hand.sphere_center(),
+            sphere_radius: hand.sphere_radius(),
+            pinch_strength: hand.pinch_strength(),
+            grab_strength: hand.grab_strength(),
+            confidence: hand.confidence(),
+            time_visible: hand.time_visible(),
+            arm: hand.arm().map(|arm| (&arm).into()),
+            fingers: hand
+                .fingers()
+                .iter()
+                .filter_map(|f| {
+                    (&f).try_into()
+                        .map_err(|e| log::warn!("dropping finger {}: {}", f.id(), e))
+                        .ok()
+                })
+                .collect(),
+            motion: None,
+        }
+    }
+}
+
+/// An owned copy of an [`Arm`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArmData {
+    pub elbow_position: Vector,
+    pub wrist_position: Vector,
+    pub center: Vector,
+    pub direction: Vector,
+    pub width: f32,
+    pub basis: Matrix,
+}
+
+impl From<&Arm> for ArmData {
+    fn from(arm: &Arm) -> Self {
+        Self {
+            elbow_position: arm.elbow_position(),
+            wrist_position: arm.wrist_position(),
+            center: arm.center(),
+            direction: arm.direction(),
+            width: arm.width(),
+            basis: arm.basis(),
+        }
+    }
+}
+
+/// An owned copy of a [`Pointable`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointableData {
+    pub id: i32,
+    /// The ID of the hand this pointable is attached to, if any.
+    pub hand_id: Option<i32>,
+    pub tip_position: Vector,
+    pub stabilized_tip_position: Vector,
+    pub tip_velocity: Vector,
+    pub direction: Vector,
+    pub width: f32,
+    pub length: f32,
+    pub is_tool: bool,
+    pub is_extended: bool,
+    pub touch_zone: TouchZone,
+    pub touch_distance: f32,
+    pub time_visible: Duration,
+}
+
+impl From<&Pointable> for PointableData {
+    fn from(pointable: &Pointable) -> Self {
+        Self {
+            id: pointable.id(),
+            hand_id: pointable.hand().map(|hand| hand.id()),
+            tip_position: pointable.tip_position(),
+            stabilized_tip_position: pointable.stabilized_tip_position(),
+            tip_velocity: pointable.tip_velocity(),
+            direction: pointable.direction(),
+            width: pointable.width(),
+            length: pointable.length(),
+            is_tool: pointable.is_tool(),
+            is_extended: pointable.is_extended(),
+            touch_zone: pointable.touch_zone(),
+            touch_distance: pointable.touch_distance(),
+            time_visible: pointable.time_visible(),
+        }
+    }
+}
+
+/// An owned copy of a [`Finger`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FingerData {
+    pub finger_type: FingerType,
+    pub pointable: PointableData,
+    /// The bones of the finger, ordered from the wrist towards the tip.
+    pub bones: [BoneData; 4],
+}
+
+impl FingerData {
+    /// Returns the bone of the given type.
+    pub fn bone(&self, bone_type: BoneType) -> &BoneData {
+        &self.bones[bone_type as usize]
+    }
+}
+
+impl TryFrom<&Finger> for FingerData {
+    type Error = UnknownType;
+
+    fn try_from(finger: &Finger) -> Result<Self, Self::Error> {
+        let [metacarpal, proximal, intermediate, distal] =
+            finger.bones().map(|bone| BoneData::try_from(&bone));
+        Ok(Self {
+            finger_type: finger.finger_type().ok_or(UnknownType("finger type"))?,
+            pointable: (&**finger).into(),
+            bones: [metacarpal?, proximal?, intermediate?, distal?],
+        })
+    }
+}
+
+/// An owned copy of a [`Bone`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoneData {
+    pub bone_type: BoneType,
+    pub prev_joint: Vector,
+    pub next_joint: Vector,
+    pub center: Vector,
+    pub direction: Vector,
+    pub length: f32,
+    pub width: f32,
+    pub basis: Matrix,
+}
+
+impl TryFrom<&Bone> for BoneData {
+    type Error = UnknownType;
+
+    fn try_from(bone: &Bone) -> Result<Self, Self::Error> {
+        Ok(Self {
+            bone_type: bone.bone_type().ok_or(UnknownType("bone type"))?,
+            prev_joint: bone.prev_joint(),
+            next_joint: bone.next_joint(),
+            center: bone.center(),
+            direction: bone.direction(),
+            length: bone.length(),
+            width: bone.width(),
+            basis: bone.basis(),
+        })
+    }
+}
+
+/// An owned copy of a [`Gesture`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GestureData {
+    pub id: i32,
+    pub state: GestureState,
+    pub duration: Duration,
+    /// The IDs of the hands performing the gesture.
+    pub hand_ids: Vec<i32>,
+    /// The IDs of the fingers and tools performing the gesture.
+    pub pointable_ids: Vec<i32>,
+    pub details: GestureDetails,
+}
+
+impl GestureData {
+    /// Returns the type of this gesture.
+    pub fn gesture_type(&self) -> GestureType {
+        match self.details {
+            GestureDetails::Swipe { .. } => GestureType::Swipe,
+            GestureDetails::Circle { .. } => GestureType::Circle,
+            GestureDetails::ScreenTap { .. } => GestureType::ScreenTap,
+            GestureDetails::KeyTap { .. } => GestureType::KeyTap,
+        }
+    }
+}
+
+impl TryFrom<&Gesture> for GestureData {
+    type Error = UnknownType;
+
+    fn try_from(gesture: &Gesture) -> Result<Self, Self::Error> {
+        let gesture_type = gesture.gesture_type().ok_or(UnknownType("gesture type"))?;
+        let details = match gesture_type {
+            GestureType::Swipe => {
+                let swipe = gesture.as_swipe().unwrap();
+                GestureDetails::Swipe {
+                    start_position: swipe.start_position(),
+                    position: swipe.position(),
+                    direction: swipe.direction(),
+                    speed: swipe.speed(),
+                    pointable_id: swipe.pointable().map(|p| p.id()),
+                }
+            }
+            GestureType::Circle => {
+                let circle = gesture.as_circle().unwrap();
+                GestureDetails::Circle {
+                    center: circle.center(),
+                    normal: circle.normal(),
+                    radius: circle.radius(),
+                    progress: circle.progress(),
+                    is_clockwise: circle.is_clockwise(),
+                    pointable_id: circle.pointable().map(|p| p.id()),
+                }
+            }
+            GestureType::ScreenTap => {
+                let tap = gesture.as_screen_tap().unwrap();
+                GestureDetails::ScreenTap {
+                    position: tap.position(),
+                    direction: tap.direction(),
+                    progress: tap.progress(),
+                    pointable_id: tap.pointable().map(|p| p.id()),
+                }
+            }
+            GestureType::KeyTap => {
+                let tap = gesture.as_key_tap().unwrap();
+                GestureDetails::KeyTap {
+                    position: tap.position(),
+                    direction: tap.direction(),
+                    progress: tap.progress(),
+                    pointable_id: tap.pointable().map(|p| p.id()),
+                }
+            }
+        };
+
+        Ok(Self {
+            id: gesture.id(),
+            state: gesture.state().ok_or(UnknownType("gesture state"))?,
+            duration: gesture.duration(),
+            hand_ids: gesture.hands().iter().map(|hand| hand.id()).collect(),
+            pointable_ids: gesture.pointables().iter().map(|p| p.id()).collect(),
+            details,
+        })
+    }
+}
+
+/// The data specific to each [`GestureType`].
+///
+/// See the corresponding types in the [`gesture`][crate::gesture] module for details. The
+/// `pointable_id` is `None` if the finger performing the gesture is no longer tracked.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GestureDetails {
+    Swipe {
+        start_position: Vector,
+        position: Vector,
+        direction: Vector,
+        speed: f32,
+        pointable_id: Option<i32>,
+    },
+    Circle {
+        center: Vector,
+        normal: Vector,
+        radius: f32,
+        progress: f32,
+        is_clockwise: Option<bool>,
+        pointable_id: Option<i32>,
+    },
+    ScreenTap {
+        position: Vector,
+        direction: Vector,
+        progress: f32,
+        pointable_id: Option<i32>,
+    },
+    KeyTap {
+        position: Vector,
+        direction: Vector,
+        progress: f32,
+        pointable_id: Option<i32>,
+    },
+}
+
+/// An owned copy of an [`InteractionBox`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InteractionBoxData {
+    pub center: Vector,
+    /// The size of the box along the X axis, in millimeters.
+    pub width: f32,
+    /// The size of the box along the Y axis, in millimeters.
+    pub height: f32,
+    /// The size of the box along the Z axis, in millimeters.
+    pub depth: f32,
+}
+
+impl From<&InteractionBox> for InteractionBoxData {
+    fn from(interaction_box: &InteractionBox) -> Self {
+        Self {
+            center: interaction_box.center(),
+            width: interaction_box.width(),
+            height: interaction_box.height(),
+            depth: interaction_box.depth(),
+        }
+    }
+}
+
+impl InteractionBoxData {
+    /// Returns the size of the box along each axis.
+    pub fn size(&self) -> Vector {
+        Vector::new(self.width, self.height, self.depth)
+    }
+
+    /// Returns whether the box has no volume, eg. because the SDK did not report a valid box.
+    pub fn is_empty(&self) -> bool {
+        !(self.width > 0.0 && self.height > 0.0 && self.depth > 0.0)
+    }
+
+    /// Maps a position to the range `[0, 1]` along each axis, with 0 corresponding to the minimum
+    /// and 1 to the maximum coordinate of the box.
+    ///
+    /// If `clamp` is `true`, positions outside of the box are clamped to the range. Along axes on
+    /// which the box has no extent, all positions are mapped to 0.5.
+    pub fn normalize_point(&self, position: Vector, clamp: bool) -> Vector {
+        let normalize = |offset: f32, size: f32| {
+            if size == 0.0 {
+                0.5
+            } else {
+                offset / size + 0.5
+            }
+        };
+        let size = self.size();
+        let offset = position - self.center;
+        let normalized = Vector::new(
+            normalize(offset.x, size.x),
+            normalize(offset.y, size.y),
+            normalize(offset.z, size.z),
+        );
+        if clamp {
+            Vector::new(
+                normalized.x.clamp(0.0, 1.0),
+                normalized.y.clamp(0.0, 1.0),
+                normalized.z.clamp(0.0, 1.0),
+            )
+        } else {
+            normalized
+        }
+    }
+
+    /// Performs the inverse of [`InteractionBoxData::normalize_point`], mapping a normalized
+    /// position back into the Leap Motion coordinate system.
+    pub fn denormalize_point(&self, normalized: Vector) -> Vector {
+        let size = self.size();
+        self.center
+            + Vector::new(
+                (normalized.x - 0.5) * size.x,
+                (normalized.y - 0.5) * size.y,
+                (normalized.z - 0.5) * size.z,
+            )
+    }
+}
+
+/// The error returned when copying a finger, bone or gesture whose type is not known to this
+/// library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownType(&'static str);
+
+impl fmt::Display for UnknownType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown {}", self.0)
+    }
+}
+
+impl Error for UnknownType {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_point() {
+        let mut box_ = fixtures::frame(1, Vec::new()).interaction_box;
+        let max = box_.center + box_.size() / 2.0;
+        assert_eq!(box_.normalize_point(max, true), Vector::new(1.0, 1.0, 1.0));
+        assert_eq!(box_.denormalize_point(Vector::new(1.0, 1.0, 1.0)), max);
+        assert_eq!(
+            box_.normalize_point(max * 2.0, true),
+            Vector::new(1.0, 1.0, 1.0)
+        );
+        assert!(!box_.is_empty());
+
+        box_.depth = 0.0;
+        assert!(box_.is_empty());
+        assert_eq!(box_.normalize_point(max, false), Vector::new(1.0, 1.0, 0.5));
+    }
+}
+
+/// Synthetic tracking data for tests.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::*;
+
+    /// Returns a frame containing `hands`, whose fingers are also listed as its pointables.
+    pub(crate) fn frame(id: i64, hands: Vec<HandData>) -> FrameData {
+        FrameData {
+            id,
+            timestamp: Timestamp::from_raw(id * 10_000),
+            frames_per_second: 100.0,
+            pointables: hands
+                .iter()
+                .flat_map(|hand| hand.fingers.iter().map(|finger| finger.pointable.clone()))
+                .collect(),
+            hands,
+            gestures: Vec::new(),
+            interaction_box: InteractionBoxData {
+                center: Vector::new(0.0, 200.0, 0.0),
+                width: 200.0,
+                height: 200.0,
+                depth: 100.0,
+            },
+            motion: None,
+        }
+    }
+
+    /// Returns an open hand pointing forward, with its palm at `palm_position`.
+    ///
+    /// The fingers have the IDs `id * 10` (thumb) to `id * 10 + 4` (pinky).
+    pub(crate) fn hand(id: i32, is_left: bool, palm_position: Vector) -> HandData {
+        let fingers = [
+            FingerType::Thumb,
+            FingerType::Index,
+            FingerType::Middle,
+            FingerType::Ring,
+            FingerType::Pinky,
+        ];
+        let side = if is_left { Vector::RIGHT } else { Vector::LEFT };
+        HandData {
+            id,
+            is_left,
+            palm_position,
+            stabilized_palm_position: palm_position,
+            palm_velocity: Vector::ZERO,
+            palm_normal: Vector::new(0.0, -1.0, 0.0),
+            palm_width: 80.0,
+            direction: Vector::FORWARD,
+            basis: Matrix::IDENTITY,
+            wrist_position: palm_position + Vector::BACKWARD * 50.0,
+            sphere_center: palm_position + Vector::UP * 50.0,
+            sphere_radius: 100.0,
+            pinch_strength: 0.0,
+            grab_strength: 0.0,
+            confidence: 1.0,
+            time_visible: Duration::from_secs(1),
+            arm: Some(ArmData {
+                elbow_position: palm_position + Vector::BACKWARD * 300.0,
+                wrist_position: palm_position + Vector::BACKWARD * 50.0,
+                center: palm_position + Vector::BACKWARD * 175.0,
+                direction: Vector::FORWARD,
+                width: 60.0,
+                basis: Matrix::IDENTITY,
+            }),
+            fingers: (0..)
+                .zip(fingers)
+                .map(|(i, finger_type)| {
+                    let base = palm_position + side * (20.0 * (i as f32 - 2.0));
+                    finger(id, id * 10 + i, finger_type, base)
+                })
+                .collect(),
+            motion: None,
+        }
+    }
+
+    /// Returns an extended finger pointing forward from `base`.
+    fn finger(hand_id: i32, id: i32, finger_type: FingerType, base: Vector) -> FingerData {
+        let bone = |i: usize, bone_type| {
+            let prev_joint = base + Vector::FORWARD * (20.0 * i as f32);
+            let next_joint = prev_joint + Vector::FORWARD * 20.0;
+            BoneData {
+                bone_type,
+                prev_joint,
+                next_joint,
+                center: (prev_joint + next_joint) / 2.0,
+                direction: Vector::FORWARD,
+                length: 20.0,
+                width: 15.0,
+                basis: Matrix::IDENTITY,
+            }
+        };
+        let tip_position = base + Vector::FORWARD * 80.0;
+        FingerData {
+            finger_type,
+            pointable: PointableData {
+                id,
+                hand_id: Some(hand_id),
+                tip_position,
+                stabilized_tip_position: tip_position,
+                tip_velocity: Vector::ZERO,
+                direction: Vector::FORWARD,
+                width: 15.0,
+                length: 60.0,
+                is_tool: false,
+                is_extended: true,
+                touch_zone: TouchZone::Hovering,
+                touch_distance: 0.5,
+                time_visible: Duration::from_secs(1),
+            },
+            bones: [
+                bone(0, BoneType::Metacarpal),
+                bone(1, BoneType::Proximal),
+                bone(2, BoneType::Intermediate),
+                bone(3, BoneType::Distal),
+            ],
+        }
+    }
+}
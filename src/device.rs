@@ -0,0 +1,136 @@
+//! Information about the connected Leap Motion devices.
+//!
+//! Devices that failed to initialize are not reported: the `Leap.h` wrapped by `leapcpp-sys` has no
+//! `FailedDevice` API. Such a device is simply missing from
+//! [`ControllerRef::devices`][crate::ControllerRef::devices], and
+//! [`ControllerRef::is_connected`][crate::ControllerRef::is_connected] returns `false`.
+
+use std::ffi::CStr;
+
+use crate::{ffi, list::ffi_list, sys, Matrix, Vector};
+
+ffi_list! {
+    /// A list of [`Device`]s, as returned by [`ControllerRef::devices`][crate::ControllerRef::devices].
+    pub struct DeviceList(sys::Leap_DeviceList) {
+        count: sys::Leap_DeviceList_count,
+        index: ffi::Leap_DeviceList_operator_index,
+    }
+
+    /// An iterator over the [`Device`]s in a [`DeviceList`].
+    pub struct DeviceListIterator -> Device;
+}
+
+/// A physical Leap Motion device.
+///
+/// There are no `is_smudged` and `is_lighting_bad` methods: later SDKs report those conditions,
+/// but the `Leap.h` of SDK 2.3.1 wrapped by `leapcpp-sys` has no such API.
+pub struct Device {
+    inner: Box<sys::Leap_Device>,
+}
+
+impl Device {
+    pub(crate) fn from_raw(inner: Box<sys::Leap_Device>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the angle of view along the long dimension of the device, in radians.
+    pub fn horizontal_view_angle(&self) -> f32 {
+        unsafe { sys::Leap_Device_horizontalViewAngle(&*self.inner) }
+    }
+
+    /// Returns the angle of view along the short dimension of the device, in radians.
+    pub fn vertical_view_angle(&self) -> f32 {
+        unsafe { sys::Leap_Device_verticalViewAngle(&*self.inner) }
+    }
+
+    /// Returns the maximum reliable tracking range from the center of the device, in millimeters.
+    pub fn range(&self) -> f32 {
+        unsafe { sys::Leap_Device_range(&*self.inner) }
+    }
+
+    /// Returns the distance between the centers of the device's cameras, in millimeters.
+    pub fn baseline(&self) -> f32 {
+        unsafe { sys::Leap_Device_baseline(&*self.inner) }
+    }
+
+    /// Returns the distance from `position` to the nearest edge of the device's view, in
+    /// millimeters.
+    ///
+    /// Positions outside of the field of view yield a distance of 0.
+    pub fn distance_to_boundary(&self, position: Vector) -> f32 {
+        unsafe { sys::Leap_Device_distanceToBoundary(&*self.inner, &position.to_raw()) }
+    }
+
+    /// Returns whether the device is embedded in another product, such as a laptop or keyboard.
+    pub fn is_embedded(&self) -> bool {
+        unsafe { sys::Leap_Device_isEmbedded(&*self.inner) }
+    }
+
+    /// Returns whether the device is currently streaming tracking data.
+    pub fn is_streaming(&self) -> bool {
+        unsafe { sys::Leap_Device_isStreaming(&*self.inner) }
+    }
+
+    /// Returns whether the device is mounted upside down.
+    pub fn is_flipped(&self) -> bool {
+        unsafe { sys::Leap_Device_isFlipped(&*self.inner) }
+    }
+
+    /// Returns the type of this device, or `None` if the SDK reports a type this library does not
+    /// know.
+    pub fn device_type(&self) -> Option<DeviceType> {
+        DeviceType::from_raw(unsafe { sys::Leap_Device_type(&*self.inner) })
+    }
+
+    /// Returns the serial number of the device.
+    pub fn serial_number(&self) -> String {
+        unsafe {
+            let raw = ffi::Leap_Device_serialNumberCString(&*self.inner);
+            let serial = CStr::from_ptr(raw).to_string_lossy().into_owned();
+            sys::Leap_Interface_deleteCString(raw);
+            serial
+        }
+    }
+
+    /// Returns the position of the device, relative to the origin of the tracking data.
+    pub fn position(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Device_position(&*self.inner) })
+    }
+
+    /// Returns the orientation of the device.
+    pub fn orientation(&self) -> Matrix {
+        Matrix::from_raw(unsafe { sys::Leap_Device_orientation(&*self.inner) })
+    }
+}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        // No `Device` destructor, call superclass dtor instead.
+        unsafe {
+            sys::Leap_Interface_Interface_destructor((&mut *self.inner) as *mut _ as _);
+        }
+    }
+}
+
+/// The kind of product a [`Device`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum DeviceType {
+    /// A standalone Leap Motion Controller.
+    Peripheral = sys::Leap_Device_Type_TYPE_PERIPHERAL,
+    /// A device embedded in a laptop.
+    Laptop = sys::Leap_Device_Type_TYPE_LAPTOP,
+    /// A device embedded in a keyboard.
+    Keyboard = sys::Leap_Device_Type_TYPE_KEYBOARD,
+}
+
+impl DeviceType {
+    fn from_raw(raw: sys::Leap_Device_Type) -> Option<Self> {
+        match raw {
+            sys::Leap_Device_Type_TYPE_PERIPHERAL => Some(Self::Peripheral),
+            sys::Leap_Device_Type_TYPE_LAPTOP => Some(Self::Laptop),
+            sys::Leap_Device_Type_TYPE_KEYBOARD => Some(Self::Keyboard),
+            _ => None,
+        }
+    }
+}
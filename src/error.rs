@@ -0,0 +1,46 @@
+use std::fmt;
+
+use crate::Policy;
+
+/// The error type of the fallible operations of this library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The SDK refused to register a [`Listener`][crate::Listener].
+    ListenerRegistration,
+    /// A [`ListenerHandle`][crate::ListenerHandle] does not belong to a listener of the controller.
+    UnknownListener,
+    /// The SDK refused to remove a [`Listener`][crate::Listener].
+    ListenerRemoval,
+    /// A removed [`Listener`][crate::Listener] was not notified of its removal in time.
+    ListenerExitTimeout,
+    /// The operation requires the connection to leapd, which is not established.
+    ServiceNotConnected,
+    /// leapd did not apply a policy within the timeout passed to
+    /// [`ControllerRef::set_policy_and_wait`][crate::ControllerRef::set_policy_and_wait].
+    ///
+    /// This happens when the policy is not permitted by the service configuration (eg. when
+    /// [`Policy::Images`] is set, but images are disabled in the Leap Motion control panel).
+    PolicyNotApplied(Policy),
+    /// A frame did not contain valid tracking data.
+    InvalidFrame,
+    /// Serialized frame data could not be decoded.
+    Deserialize,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ListenerRegistration => f.write_str("failed to register listener"),
+            Error::UnknownListener => f.write_str("unknown listener"),
+            Error::ListenerRemoval => f.write_str("failed to remove listener"),
+            Error::ListenerExitTimeout => f.write_str("timed out waiting for listener to exit"),
+            Error::ServiceNotConnected => f.write_str("not connected to leapd"),
+            Error::PolicyNotApplied(policy) => write!(f, "policy {:?} was not applied", policy),
+            Error::InvalidFrame => f.write_str("invalid frame"),
+            Error::Deserialize => f.write_str("invalid serialized frame data"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
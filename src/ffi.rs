@@ -0,0 +1,149 @@
+//! Declarations for `libLeap` symbols that `leapcpp-sys` does not bind.
+//!
+//! bindgen skips overloaded operators and the private `*CString` helpers behind the SDK's
+//! `std::string` accessors, so they are declared here by hand.
+//! Like the rest of the bindings, functions returning a non-trivial class by value take an explicit
+//! out-pointer as their first argument.
+
+#![allow(non_snake_case)]
+
+use std::os::raw::c_char;
+
+use crate::sys;
+
+extern "C" {
+    #[link_name = "\u{1}_ZNK4Leap8HandListixEi"]
+    pub fn Leap_HandList_operator_index(
+        out: *mut sys::Leap_Hand,
+        this: *const sys::Leap_HandList,
+        index: i32,
+    );
+}
+
+extern "C" {
+    #[link_name = "\u{1}_ZNK4Leap13PointableListixEi"]
+    pub fn Leap_PointableList_operator_index(
+        out: *mut sys::Leap_Pointable,
+        this: *const sys::Leap_PointableList,
+        index: i32,
+    );
+}
+
+extern "C" {
+    #[link_name = "\u{1}_ZNK4Leap10FingerListixEi"]
+    pub fn Leap_FingerList_operator_index(
+        out: *mut sys::Leap_Finger,
+        this: *const sys::Leap_FingerList,
+        index: i32,
+    );
+}
+
+extern "C" {
+    #[link_name = "\u{1}_ZNK4Leap11GestureListixEi"]
+    pub fn Leap_GestureList_operator_index(
+        out: *mut sys::Leap_Gesture,
+        this: *const sys::Leap_GestureList,
+        index: i32,
+    );
+}
+
+extern "C" {
+    #[link_name = "\u{1}_ZNK4Leap10DeviceListixEi"]
+    pub fn Leap_DeviceList_operator_index(
+        out: *mut sys::Leap_Device,
+        this: *const sys::Leap_DeviceList,
+        index: i32,
+    );
+}
+
+extern "C" {
+    /// The returned string has to be freed with `Leap_Interface_deleteCString`.
+    #[link_name = "\u{1}_ZNK4Leap6Device19serialNumberCStringEv"]
+    pub fn Leap_Device_serialNumberCString(this: *const sys::Leap_Device) -> *const c_char;
+}
+
+extern "C" {
+    #[link_name = "\u{1}_ZNK4Leap6Config11typeCStringEPKc"]
+    pub fn Leap_Config_typeCString(
+        this: *const sys::Leap_Config,
+        key: *const c_char,
+    ) -> sys::Leap_Config_ValueType;
+}
+
+extern "C" {
+    #[link_name = "\u{1}_ZNK4Leap6Config14getBoolCStringEPKc"]
+    pub fn Leap_Config_getBoolCString(this: *const sys::Leap_Config, key: *const c_char) -> bool;
+}
+
+extern "C" {
+    #[link_name = "\u{1}_ZN4Leap6Config14setBoolCStringEPKcb"]
+    pub fn Leap_Config_setBoolCString(
+        this: *mut sys::Leap_Config,
+        key: *const c_char,
+        value: bool,
+    ) -> bool;
+}
+
+extern "C" {
+    #[link_name = "\u{1}_ZNK4Leap6Config15getInt32CStringEPKc"]
+    pub fn Leap_Config_getInt32CString(this: *const sys::Leap_Config, key: *const c_char) -> i32;
+}
+
+extern "C" {
+    #[link_name = "\u{1}_ZN4Leap6Config15setInt32CStringEPKci"]
+    pub fn Leap_Config_setInt32CString(
+        this: *mut sys::Leap_Config,
+        key: *const c_char,
+        value: i32,
+    ) -> bool;
+}
+
+extern "C" {
+    #[link_name = "\u{1}_ZNK4Leap6Config15getFloatCStringEPKc"]
+    pub fn Leap_Config_getFloatCString(this: *const sys::Leap_Config, key: *const c_char) -> f32;
+}
+
+extern "C" {
+    #[link_name = "\u{1}_ZN4Leap6Config15setFloatCStringEPKcf"]
+    pub fn Leap_Config_setFloatCString(
+        this: *mut sys::Leap_Config,
+        key: *const c_char,
+        value: f32,
+    ) -> bool;
+}
+
+extern "C" {
+    /// The returned string has to be freed with `Leap_Interface_deleteCString`.
+    #[link_name = "\u{1}_ZNK4Leap6Config16getStringCStringEPKc"]
+    pub fn Leap_Config_getStringCString(
+        this: *const sys::Leap_Config,
+        key: *const c_char,
+    ) -> *const c_char;
+}
+
+extern "C" {
+    #[link_name = "\u{1}_ZN4Leap6Config16setStringCStringEPKcS2_"]
+    pub fn Leap_Config_setStringCString(
+        this: *mut sys::Leap_Config,
+        key: *const c_char,
+        value: *const c_char,
+    ) -> bool;
+}
+
+extern "C" {
+    /// The returned buffer has to be freed with `Leap_Interface_deleteCString`.
+    #[link_name = "\u{1}_ZNK4Leap5Frame16serializeCStringERm"]
+    pub fn Leap_Frame_serializeCString(
+        this: *const sys::Leap_Frame,
+        length: *mut usize,
+    ) -> *const c_char;
+}
+
+extern "C" {
+    #[link_name = "\u{1}_ZN4Leap5Frame18deserializeCStringEPKcm"]
+    pub fn Leap_Frame_deserializeCString(
+        this: *mut sys::Leap_Frame,
+        data: *const c_char,
+        length: usize,
+    );
+}
@@ -0,0 +1,338 @@
+//! Gestures detected by the Leap Motion service.
+//!
+//! Gestures have to be enabled via [`ControllerRef::enable_gesture`] before they are reported.
+//!
+//! [`ControllerRef::enable_gesture`]: crate::ControllerRef::enable_gesture
+
+use std::{f32::consts::FRAC_PI_2, mem::MaybeUninit, ops::Deref, time::Duration};
+
+use crate::{
+    ffi,
+    hand::HandList,
+    list::ffi_list,
+    pointable::{Pointable, PointableList},
+    sys, GestureState, GestureType, Vector,
+};
+
+ffi_list! {
+    /// A list of [`Gesture`]s.
+    pub struct GestureList(sys::Leap_GestureList) {
+        count: sys::Leap_GestureList_count,
+        index: ffi::Leap_GestureList_operator_index,
+    }
+
+    /// An iterator over the [`Gesture`]s in a [`GestureList`].
+    pub struct GestureListIterator -> Gesture;
+}
+
+/// A gesture performed by the user.
+///
+/// Use the `as_*` methods to access the data specific to each type of gesture.
+pub struct Gesture {
+    inner: Box<sys::Leap_Gesture>,
+}
+
+impl Gesture {
+    pub(crate) fn from_raw(inner: Box<sys::Leap_Gesture>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the gesture's unique ID.
+    ///
+    /// The ID stays the same across frames for as long as the gesture is in progress.
+    pub fn id(&self) -> i32 {
+        unsafe { sys::Leap_Gesture_id(&*self.inner) }
+    }
+
+    /// Returns the type of this gesture, or `None` if the SDK reports a type this library does
+    /// not know.
+    pub fn gesture_type(&self) -> Option<GestureType> {
+        let raw = unsafe { sys::Leap_Gesture_type(&*self.inner) };
+        GestureType::from_raw(raw)
+    }
+
+    /// Returns the progression state of this gesture, or `None` if the SDK reports a state this
+    /// library does not know.
+    pub fn state(&self) -> Option<GestureState> {
+        let raw = unsafe { sys::Leap_Gesture_state(&*self.inner) };
+        GestureState::from_raw(raw)
+    }
+
+    /// Returns how long the gesture has been in progress.
+    pub fn duration(&self) -> Duration {
+        let micros = unsafe { sys::Leap_Gesture_duration(&*self.inner) };
+        Duration::from_micros(micros.max(0) as u64)
+    }
+
+    /// Returns the hands performing this gesture.
+    pub fn hands(&self) -> HandList {
+        unsafe {
+            let mut hands = Box::new(MaybeUninit::uninit());
+            sys::Leap_Gesture_hands(hands.as_mut_ptr(), &*self.inner);
+            HandList::from_raw(crate::init_box(hands))
+        }
+    }
+
+    /// Returns the fingers and tools performing this gesture.
+    pub fn pointables(&self) -> PointableList {
+        unsafe {
+            let mut pointables = Box::new(MaybeUninit::uninit());
+            sys::Leap_Gesture_pointables(pointables.as_mut_ptr(), &*self.inner);
+            PointableList::from_raw(crate::init_box(pointables))
+        }
+    }
+
+    /// Returns whether this object contains valid tracking data.
+    pub(crate) fn is_valid(&self) -> bool {
+        unsafe { sys::Leap_Gesture_isValid(&*self.inner) }
+    }
+
+    /// Returns the swipe-specific data, if this is a [`GestureType::Swipe`] gesture.
+    pub fn as_swipe(&self) -> Option<SwipeGesture> {
+        (self.gesture_type() == Some(GestureType::Swipe)).then(|| SwipeGesture {
+            gesture: unsafe { self.convert(sys::Leap_SwipeGesture_SwipeGesture1) },
+        })
+    }
+
+    /// Returns the circle-specific data, if this is a [`GestureType::Circle`] gesture.
+    pub fn as_circle(&self) -> Option<CircleGesture> {
+        (self.gesture_type() == Some(GestureType::Circle)).then(|| CircleGesture {
+            gesture: unsafe { self.convert(sys::Leap_CircleGesture_CircleGesture1) },
+        })
+    }
+
+    /// Returns the screen-tap-specific data, if this is a [`GestureType::ScreenTap`] gesture.
+    pub fn as_screen_tap(&self) -> Option<ScreenTapGesture> {
+        (self.gesture_type() == Some(GestureType::ScreenTap)).then(|| ScreenTapGesture {
+            gesture: unsafe { self.convert(sys::Leap_ScreenTapGesture_ScreenTapGesture1) },
+        })
+    }
+
+    /// Returns the key-tap-specific data, if this is a [`GestureType::KeyTap`] gesture.
+    pub fn as_key_tap(&self) -> Option<KeyTapGesture> {
+        (self.gesture_type() == Some(GestureType::KeyTap)).then(|| KeyTapGesture {
+            gesture: unsafe { self.convert(sys::Leap_KeyTapGesture_KeyTapGesture1) },
+        })
+    }
+
+    /// Invokes one of the subclass constructors that take a `const Leap::Gesture&`.
+    unsafe fn convert<T>(
+        &self,
+        ctor: unsafe extern "C" fn(*mut T, *const sys::Leap_Gesture),
+    ) -> Gesture {
+        let mut raw = Box::new(MaybeUninit::<T>::uninit());
+        ctor(raw.as_mut_ptr(), &*self.inner);
+        // The gesture subclasses add no fields of their own, so the allocation can be shared.
+        Gesture::from_raw(Box::from_raw(Box::into_raw(raw) as *mut sys::Leap_Gesture))
+    }
+
+    fn raw<T>(&self) -> *const T {
+        &*self.inner as *const _ as *const T
+    }
+}
+
+impl Drop for Gesture {
+    fn drop(&mut self) {
+        // No `Gesture` destructor, call superclass dtor instead.
+        unsafe {
+            sys::Leap_Interface_Interface_destructor((&mut *self.inner) as *mut _ as _);
+        }
+    }
+}
+
+/// A linear movement of a hand.
+pub struct SwipeGesture {
+    gesture: Gesture,
+}
+
+impl SwipeGesture {
+    fn raw(&self) -> *const sys::Leap_SwipeGesture {
+        self.gesture.raw()
+    }
+
+    /// Returns the position where the swipe began.
+    pub fn start_position(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_SwipeGesture_startPosition(self.raw()) })
+    }
+
+    /// Returns the current position of the swipe.
+    pub fn position(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_SwipeGesture_position(self.raw()) })
+    }
+
+    /// Returns the unit direction vector of the swipe.
+    pub fn direction(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_SwipeGesture_direction(self.raw()) })
+    }
+
+    /// Returns the speed of the swipe, in millimeters per second.
+    pub fn speed(&self) -> f32 {
+        unsafe { sys::Leap_SwipeGesture_speed(self.raw()) }
+    }
+
+    /// Returns the finger performing the swipe, if it is still tracked.
+    pub fn pointable(&self) -> Option<Pointable> {
+        let pointable = unsafe {
+            let mut pointable = Box::new(MaybeUninit::uninit());
+            sys::Leap_SwipeGesture_pointable(pointable.as_mut_ptr(), self.raw());
+            Pointable::from_raw(crate::init_box(pointable))
+        };
+        pointable.is_valid().then_some(pointable)
+    }
+}
+
+impl Deref for SwipeGesture {
+    type Target = Gesture;
+
+    fn deref(&self) -> &Self::Target {
+        &self.gesture
+    }
+}
+
+/// A circular movement of a finger.
+pub struct CircleGesture {
+    gesture: Gesture,
+}
+
+impl CircleGesture {
+    fn raw(&self) -> *const sys::Leap_CircleGesture {
+        self.gesture.raw()
+    }
+
+    /// Returns the center point of the circle.
+    pub fn center(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_CircleGesture_center(self.raw()) })
+    }
+
+    /// Returns the normal vector of the circle.
+    pub fn normal(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_CircleGesture_normal(self.raw()) })
+    }
+
+    /// Returns the radius of the circle.
+    pub fn radius(&self) -> f32 {
+        unsafe { sys::Leap_CircleGesture_radius(self.raw()) }
+    }
+
+    /// Returns the number of times the finger has traversed the circle.
+    ///
+    /// This is a fractional value: 0.5 means the finger has completed half a circle.
+    pub fn progress(&self) -> f32 {
+        unsafe { sys::Leap_CircleGesture_progress(self.raw()) }
+    }
+
+    /// Returns whether the finger is moving clockwise, as seen by the user.
+    ///
+    /// The direction is derived from the finger, so this is `None` if the finger is no longer
+    /// tracked.
+    pub fn is_clockwise(&self) -> Option<bool> {
+        let pointable = self.pointable()?;
+        Some(pointable.direction().angle_to(self.normal()) <= FRAC_PI_2)
+    }
+
+    /// Returns the finger performing the circle gesture, if it is still tracked.
+    pub fn pointable(&self) -> Option<Pointable> {
+        let pointable = unsafe {
+            let mut pointable = Box::new(MaybeUninit::uninit());
+            sys::Leap_CircleGesture_pointable(pointable.as_mut_ptr(), self.raw());
+            Pointable::from_raw(crate::init_box(pointable))
+        };
+        pointable.is_valid().then_some(pointable)
+    }
+}
+
+impl Deref for CircleGesture {
+    type Target = Gesture;
+
+    fn deref(&self) -> &Self::Target {
+        &self.gesture
+    }
+}
+
+/// A forward tapping movement of a finger, towards the screen.
+pub struct ScreenTapGesture {
+    gesture: Gesture,
+}
+
+impl ScreenTapGesture {
+    fn raw(&self) -> *const sys::Leap_ScreenTapGesture {
+        self.gesture.raw()
+    }
+
+    /// Returns the position where the tap was registered.
+    pub fn position(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_ScreenTapGesture_position(self.raw()) })
+    }
+
+    /// Returns the direction of the tapping finger.
+    pub fn direction(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_ScreenTapGesture_direction(self.raw()) })
+    }
+
+    /// Returns the progress of the tap (always 1.0, as taps are discrete).
+    pub fn progress(&self) -> f32 {
+        unsafe { sys::Leap_ScreenTapGesture_progress(self.raw()) }
+    }
+
+    /// Returns the finger performing the tap, if it is still tracked.
+    pub fn pointable(&self) -> Option<Pointable> {
+        let pointable = unsafe {
+            let mut pointable = Box::new(MaybeUninit::uninit());
+            sys::Leap_ScreenTapGesture_pointable(pointable.as_mut_ptr(), self.raw());
+            Pointable::from_raw(crate::init_box(pointable))
+        };
+        pointable.is_valid().then_some(pointable)
+    }
+}
+
+impl Deref for ScreenTapGesture {
+    type Target = Gesture;
+
+    fn deref(&self) -> &Self::Target {
+        &self.gesture
+    }
+}
+
+/// A downward tapping movement of a finger, as if pressing a key.
+pub struct KeyTapGesture {
+    gesture: Gesture,
+}
+
+impl KeyTapGesture {
+    fn raw(&self) -> *const sys::Leap_KeyTapGesture {
+        self.gesture.raw()
+    }
+
+    /// Returns the position where the tap was registered.
+    pub fn position(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_KeyTapGesture_position(self.raw()) })
+    }
+
+    /// Returns the direction of the tapping finger.
+    pub fn direction(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_KeyTapGesture_direction(self.raw()) })
+    }
+
+    /// Returns the progress of the tap (always 1.0, as taps are discrete).
+    pub fn progress(&self) -> f32 {
+        unsafe { sys::Leap_KeyTapGesture_progress(self.raw()) }
+    }
+
+    /// Returns the finger performing the tap, if it is still tracked.
+    pub fn pointable(&self) -> Option<Pointable> {
+        let pointable = unsafe {
+            let mut pointable = Box::new(MaybeUninit::uninit());
+            sys::Leap_KeyTapGesture_pointable(pointable.as_mut_ptr(), self.raw());
+            Pointable::from_raw(crate::init_box(pointable))
+        };
+        pointable.is_valid().then_some(pointable)
+    }
+}
+
+impl Deref for KeyTapGesture {
+    type Target = Gesture;
+
+    fn deref(&self) -> &Self::Target {
+        &self.gesture
+    }
+}
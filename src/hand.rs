@@ -0,0 +1,290 @@
+//! Hand tracking data.
+
+use std::{mem::MaybeUninit, time::Duration};
+
+use crate::{
+    ffi,
+    list::ffi_list,
+    pointable::{Finger, FingerList, Pointable, PointableList},
+    sys, Frame, Matrix, Vector,
+};
+
+ffi_list! {
+    /// A list of [`Hand`]s detected in a [`Frame`].
+    pub struct HandList(sys::Leap_HandList) {
+        count: sys::Leap_HandList_count,
+        index: ffi::Leap_HandList_operator_index,
+    }
+
+    /// An iterator over the [`Hand`]s in a [`HandList`].
+    pub struct HandListIterator -> Hand;
+}
+
+/// A tracked hand.
+///
+/// All positions are given in millimeters, relative to the Leap Motion Controller.
+pub struct Hand {
+    inner: Box<sys::Leap_Hand>,
+}
+
+impl Hand {
+    pub(crate) fn from_raw(inner: Box<sys::Leap_Hand>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the hand's unique ID.
+    ///
+    /// The ID stays the same across frames as long as the hand stays tracked.
+    pub fn id(&self) -> i32 {
+        unsafe { sys::Leap_Hand_id(&*self.inner) }
+    }
+
+    /// Returns the fingers attached to this hand.
+    pub fn fingers(&self) -> FingerList {
+        unsafe {
+            let mut fingers = Box::new(MaybeUninit::uninit());
+            sys::Leap_Hand_fingers(fingers.as_mut_ptr(), &*self.inner);
+            FingerList::from_raw(crate::init_box(fingers))
+        }
+    }
+
+    /// Returns the fingers and tools attached to this hand.
+    pub fn pointables(&self) -> PointableList {
+        unsafe {
+            let mut pointables = Box::new(MaybeUninit::uninit());
+            sys::Leap_Hand_pointables(pointables.as_mut_ptr(), &*self.inner);
+            PointableList::from_raw(crate::init_box(pointables))
+        }
+    }
+
+    /// Returns the finger with the given ID, if it is attached to this hand.
+    pub fn finger(&self, id: i32) -> Option<Finger> {
+        let finger = unsafe {
+            let mut finger = Box::new(MaybeUninit::uninit());
+            sys::Leap_Hand_finger(finger.as_mut_ptr(), &*self.inner, id);
+            Finger::from_raw(crate::init_box(finger))
+        };
+        finger.is_valid().then_some(finger)
+    }
+
+    /// Returns the finger or tool with the given ID, if it is attached to this hand.
+    pub fn pointable(&self, id: i32) -> Option<Pointable> {
+        let pointable = unsafe {
+            let mut pointable = Box::new(MaybeUninit::uninit());
+            sys::Leap_Hand_pointable(pointable.as_mut_ptr(), &*self.inner, id);
+            Pointable::from_raw(crate::init_box(pointable))
+        };
+        pointable.is_valid().then_some(pointable)
+    }
+
+    /// Returns the center position of the palm.
+    pub fn palm_position(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Hand_palmPosition(&*self.inner) })
+    }
+
+    /// Returns the palm position with additional smoothing and stabilization applied.
+    pub fn stabilized_palm_position(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Hand_stabilizedPalmPosition(&*self.inner) })
+    }
+
+    /// Returns the velocity of the palm, in millimeters per second.
+    pub fn palm_velocity(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Hand_palmVelocity(&*self.inner) })
+    }
+
+    /// Returns the unit vector perpendicular to the palm, pointing out of the palm.
+    pub fn palm_normal(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Hand_palmNormal(&*self.inner) })
+    }
+
+    /// Returns the estimated width of the palm.
+    pub fn palm_width(&self) -> f32 {
+        unsafe { sys::Leap_Hand_palmWidth(&*self.inner) }
+    }
+
+    /// Returns the unit vector pointing from the palm towards the fingers.
+    pub fn direction(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Hand_direction(&*self.inner) })
+    }
+
+    /// Returns the orientation of the hand as a basis matrix.
+    pub fn basis(&self) -> Matrix {
+        Matrix::from_raw(unsafe { sys::Leap_Hand_basis(&*self.inner) })
+    }
+
+    /// Returns the position of the wrist.
+    pub fn wrist_position(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Hand_wristPosition(&*self.inner) })
+    }
+
+    /// Returns the arm this hand is attached to, if the service reports one.
+    pub fn arm(&self) -> Option<Arm> {
+        let arm = unsafe {
+            let mut arm = Box::new(MaybeUninit::uninit());
+            sys::Leap_Hand_arm(arm.as_mut_ptr(), &*self.inner);
+            Arm {
+                inner: crate::init_box(arm),
+            }
+        };
+        arm.is_valid().then_some(arm)
+    }
+
+    /// Returns the center of a sphere fit to the curvature of the hand.
+    pub fn sphere_center(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Hand_sphereCenter(&*self.inner) })
+    }
+
+    /// Returns the radius of a sphere fit to the curvature of the hand.
+    pub fn sphere_radius(&self) -> f32 {
+        unsafe { sys::Leap_Hand_sphereRadius(&*self.inner) }
+    }
+
+    /// Returns how strongly the hand is pinching, from 0.0 (open hand) to 1.0 (pinching).
+    pub fn pinch_strength(&self) -> f32 {
+        unsafe { sys::Leap_Hand_pinchStrength(&*self.inner) }
+    }
+
+    /// Returns how strongly the hand is grabbing, from 0.0 (open hand) to 1.0 (fist).
+    pub fn grab_strength(&self) -> f32 {
+        unsafe { sys::Leap_Hand_grabStrength(&*self.inner) }
+    }
+
+    /// Returns how well the internal hand model fits the observed data, from 0.0 to 1.0.
+    pub fn confidence(&self) -> f32 {
+        unsafe { sys::Leap_Hand_confidence(&*self.inner) }
+    }
+
+    /// Returns whether this is a left hand.
+    pub fn is_left(&self) -> bool {
+        unsafe { sys::Leap_Hand_isLeft(&*self.inner) }
+    }
+
+    /// Returns whether this is a right hand.
+    pub fn is_right(&self) -> bool {
+        unsafe { sys::Leap_Hand_isRight(&*self.inner) }
+    }
+
+    /// Returns how long this hand has been continuously tracked.
+    pub fn time_visible(&self) -> Duration {
+        let secs = unsafe { sys::Leap_Hand_timeVisible(&*self.inner) };
+        Duration::from_secs_f32(secs.max(0.0))
+    }
+
+    /// Returns the change of position of this hand between `since` and this frame, in millimeters.
+    pub fn translation(&self, since: &Frame) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Hand_translation(&*self.inner, &*since.inner) })
+    }
+
+    /// Returns the estimated probability, from 0 to 1, that the motion between `since` and this
+    /// frame is intended as a translating motion.
+    pub fn translation_probability(&self, since: &Frame) -> f32 {
+        unsafe { sys::Leap_Hand_translationProbability(&*self.inner, &*since.inner) }
+    }
+
+    /// Returns the axis of the rotation of this hand between `since` and this frame.
+    pub fn rotation_axis(&self, since: &Frame) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Hand_rotationAxis(&*self.inner, &*since.inner) })
+    }
+
+    /// Returns the angle of the rotation of this hand around [`Hand::rotation_axis`] between
+    /// `since` and this frame, in radians.
+    pub fn rotation_angle(&self, since: &Frame) -> f32 {
+        unsafe { sys::Leap_Hand_rotationAngle(&*self.inner, &*since.inner) }
+    }
+
+    /// Returns the angle of the rotation of this hand around `axis` between `since` and this frame,
+    /// in radians.
+    pub fn rotation_angle_around(&self, since: &Frame, axis: Vector) -> f32 {
+        unsafe { sys::Leap_Hand_rotationAngle1(&*self.inner, &*since.inner, &axis.to_raw()) }
+    }
+
+    /// Returns the rotation of this hand between `since` and this frame, as a transformation
+    /// matrix.
+    pub fn rotation_matrix(&self, since: &Frame) -> Matrix {
+        Matrix::from_raw(unsafe { sys::Leap_Hand_rotationMatrix(&*self.inner, &*since.inner) })
+    }
+
+    /// Returns the estimated probability, from 0 to 1, that the motion between `since` and this
+    /// frame is intended as a rotating motion.
+    pub fn rotation_probability(&self, since: &Frame) -> f32 {
+        unsafe { sys::Leap_Hand_rotationProbability(&*self.inner, &*since.inner) }
+    }
+
+    /// Returns the scaling of this hand between `since` and this frame.
+    ///
+    /// Values above 1 indicate expansion, values below 1 contraction.
+    pub fn scale_factor(&self, since: &Frame) -> f32 {
+        unsafe { sys::Leap_Hand_scaleFactor(&*self.inner, &*since.inner) }
+    }
+
+    /// Returns the estimated probability, from 0 to 1, that the motion between `since` and this
+    /// frame is intended as a scaling motion.
+    pub fn scale_probability(&self, since: &Frame) -> f32 {
+        unsafe { sys::Leap_Hand_scaleProbability(&*self.inner, &*since.inner) }
+    }
+
+    /// Returns whether this object contains valid tracking data.
+    pub(crate) fn is_valid(&self) -> bool {
+        unsafe { sys::Leap_Hand_isValid(&*self.inner) }
+    }
+}
+
+impl Drop for Hand {
+    fn drop(&mut self) {
+        // No `Hand` destructor, call superclass dtor instead.
+        unsafe {
+            sys::Leap_Interface_Interface_destructor((&mut *self.inner) as *mut _ as _);
+        }
+    }
+}
+
+/// The forearm a [`Hand`] is attached to.
+pub struct Arm {
+    inner: Box<sys::Leap_Arm>,
+}
+
+impl Arm {
+    /// Returns the position of the elbow.
+    pub fn elbow_position(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Arm_elbowPosition(&*self.inner) })
+    }
+
+    /// Returns the position of the wrist.
+    pub fn wrist_position(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Arm_wristPosition(&*self.inner) })
+    }
+
+    /// Returns the midpoint between elbow and wrist.
+    pub fn center(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Arm_center(&*self.inner) })
+    }
+
+    /// Returns the unit vector pointing from the elbow towards the wrist.
+    pub fn direction(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Arm_direction(&*self.inner) })
+    }
+
+    /// Returns the estimated width of the forearm.
+    pub fn width(&self) -> f32 {
+        unsafe { sys::Leap_Arm_width(&*self.inner) }
+    }
+
+    /// Returns the orientation of the forearm as a basis matrix.
+    pub fn basis(&self) -> Matrix {
+        Matrix::from_raw(unsafe { sys::Leap_Arm_basis(&*self.inner) })
+    }
+
+    /// Returns whether this object contains valid tracking data.
+    pub(crate) fn is_valid(&self) -> bool {
+        unsafe { sys::Leap_Arm_isValid(&*self.inner) }
+    }
+}
+
+impl Drop for Arm {
+    fn drop(&mut self) {
+        // No `Arm` destructor, call superclass dtor instead.
+        unsafe {
+            sys::Leap_Interface_Interface_destructor((&mut *self.inner) as *mut _ as _);
+        }
+    }
+}
@@ -2,23 +2,39 @@
 //!
 //! Receiving raw camera data requires enabling [`Policy::Images`][crate::Policy::Images].
 
-use std::{fmt, mem::MaybeUninit};
+use std::{error::Error, fmt, mem::MaybeUninit, sync::Arc};
 
 use crate::{sys, Timestamp};
 
 /// A list of raw camera images recorded by the Leap Motion Controller.
 pub struct ImageList {
-    raw: Box<sys::Leap_ImageList>,
+    inner: ListInner,
+}
+
+enum ListInner {
+    Leap(Box<sys::Leap_ImageList>),
+    Owned(Vec<Arc<ImageBuf>>),
 }
 
 impl ImageList {
     pub(crate) fn from_raw(raw: Box<sys::Leap_ImageList>) -> Self {
-        Self { raw }
+        Self {
+            inner: ListInner::Leap(raw),
+        }
+    }
+
+    pub(crate) fn from_bufs(bufs: Vec<Arc<ImageBuf>>) -> Self {
+        Self {
+            inner: ListInner::Owned(bufs),
+        }
     }
 
     /// Returns the number of images in the list.
     pub fn len(&self) -> usize {
-        unsafe { sys::Leap_ImageList_count(&*self.raw) as usize }
+        match &self.inner {
+            ListInner::Leap(raw) => unsafe { sys::Leap_ImageList_count(&**raw) as usize },
+            ListInner::Owned(bufs) => bufs.len(),
+        }
     }
 
     /// Returns whether this list is empty.
@@ -26,6 +42,24 @@ impl ImageList {
         self.len() == 0
     }
 
+    /// Returns the image at `index`, or `None` if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Image> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let inner = match &self.inner {
+            ListInner::Leap(raw) => unsafe {
+                let mut image = Box::new(MaybeUninit::uninit());
+                sys::Leap_RustGetImage(image.as_mut_ptr(), &**raw, index as i32);
+                ImageInner::Leap(crate::init_box(image))
+            },
+            ListInner::Owned(bufs) => ImageInner::Owned(bufs[index].clone()),
+        };
+        let image = Image { inner };
+        image.is_valid().then_some(image)
+    }
+
     /// Returns an iterator over the images in the list.
     pub fn iter(&self) -> ImageListIterator<'_> {
         ImageListIterator {
@@ -38,9 +72,11 @@ impl ImageList {
 
 impl Drop for ImageList {
     fn drop(&mut self) {
-        // No `ImageList` destructor, call superclass dtor instead.
-        unsafe {
-            sys::Leap_Interface_Interface_destructor((&mut *self.raw) as *mut _ as _);
+        if let ListInner::Leap(raw) = &mut self.inner {
+            // No `ImageList` destructor, call superclass dtor instead.
+            unsafe {
+                sys::Leap_Interface_Interface_destructor((&mut **raw) as *mut _ as _);
+            }
         }
     }
 }
@@ -56,60 +92,83 @@ impl<'a> Iterator for ImageListIterator<'a> {
     type Item = Image;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.next == self.len {
-            None
-        } else {
-            unsafe {
-                let mut image = Box::new(MaybeUninit::uninit());
-                sys::Leap_RustGetImage(image.as_mut_ptr(), &*self.list.raw, self.next as i32);
-                self.next += 1;
-                Some(Image {
-                    inner: crate::init_box(image),
-                })
+        while self.next < self.len {
+            let index = self.next;
+            self.next += 1;
+            if let Some(image) = self.list.get(index) {
+                return Some(image);
             }
         }
+
+        None
     }
 }
 
 /// A raw camera image, alongside calibration data.
 pub struct Image {
-    inner: Box<sys::Leap_Image>,
+    inner: ImageInner,
+}
+
+enum ImageInner {
+    Leap(Box<sys::Leap_Image>),
+    Owned(Arc<ImageBuf>),
 }
 
 impl Image {
-    pub fn is_valid(&self) -> bool {
-        unsafe { sys::Leap_Image_isValid(&*self.inner) }
+    /// Returns whether the SDK considers this image valid. [`ImageList::get`] never returns invalid
+    /// images.
+    fn is_valid(&self) -> bool {
+        match &self.inner {
+            ImageInner::Leap(raw) => unsafe { sys::Leap_Image_isValid(&**raw) },
+            ImageInner::Owned(_) => true,
+        }
     }
 
     pub fn sequence_id(&self) -> i64 {
-        unsafe { sys::Leap_Image_sequenceId(&*self.inner) }
+        match &self.inner {
+            ImageInner::Leap(raw) => unsafe { sys::Leap_Image_sequenceId(&**raw) },
+            ImageInner::Owned(buf) => buf.sequence_id,
+        }
     }
 
-    pub fn camera(&self) -> Camera {
-        let id = unsafe { sys::Leap_Image_id(&*self.inner) };
-
-        match id {
-            0 => Camera::Left,
-            1 => Camera::Right,
-            _ => unreachable!("encountered invalid image ID {}", id),
+    /// Returns the camera that recorded this image.
+    ///
+    /// Returns an error if the SDK reports an unknown camera.
+    pub fn camera(&self) -> Result<Camera, InvalidCameraId> {
+        match &self.inner {
+            ImageInner::Leap(raw) => Camera::try_from(unsafe { sys::Leap_Image_id(&**raw) }),
+            ImageInner::Owned(buf) => Ok(buf.camera),
         }
     }
 
     pub fn timestamp(&self) -> Timestamp {
-        let raw = unsafe { sys::Leap_Image_timestamp(&*self.inner) };
-        Timestamp::from_raw(raw)
+        match &self.inner {
+            ImageInner::Leap(raw) => {
+                Timestamp::from_raw(unsafe { sys::Leap_Image_timestamp(&**raw) })
+            }
+            ImageInner::Owned(buf) => buf.timestamp,
+        }
     }
 
     pub fn width(&self) -> usize {
-        unsafe { sys::Leap_Image_width(&*self.inner) as usize }
+        match &self.inner {
+            ImageInner::Leap(raw) => unsafe { sys::Leap_Image_width(&**raw) as usize },
+            ImageInner::Owned(buf) => buf.width,
+        }
     }
 
     pub fn height(&self) -> usize {
-        unsafe { sys::Leap_Image_height(&*self.inner) as usize }
+        match &self.inner {
+            ImageInner::Leap(raw) => unsafe { sys::Leap_Image_height(&**raw) as usize },
+            ImageInner::Owned(buf) => buf.height,
+        }
     }
 
     pub fn bytes_per_pixel(&self) -> usize {
-        unsafe { sys::Leap_Image_bytesPerPixel(&*self.inner) as usize }
+        match &self.inner {
+            ImageInner::Leap(raw) => unsafe { sys::Leap_Image_bytesPerPixel(&**raw) as usize },
+            ImageInner::Owned(buf) => buf.bytes_per_pixel,
+        }
     }
 
     pub fn data(&self) -> ImageData<'_> {
@@ -120,11 +179,15 @@ impl Image {
     }
 
     pub fn raw_data(&self) -> &[u8] {
-        let len = self.width() * self.height() * self.bytes_per_pixel();
-
-        unsafe {
-            let ptr = sys::Leap_Image_data(&*self.inner);
-            std::slice::from_raw_parts(ptr, len)
+        match &self.inner {
+            ImageInner::Leap(raw) => {
+                let len = self.width() * self.height() * self.bytes_per_pixel();
+                unsafe {
+                    let ptr = sys::Leap_Image_data(&**raw);
+                    std::slice::from_raw_parts(ptr, len)
+                }
+            }
+            ImageInner::Owned(buf) => &buf.data,
         }
     }
 
@@ -136,14 +199,17 @@ impl Image {
     }
 
     pub fn raw_distortion(&self) -> &[f32] {
-        unsafe {
-            let ptr = sys::Leap_Image_distortion(&*self.inner);
-            std::slice::from_raw_parts(ptr, self.distortion_stride() * self.distortion_height())
+        match &self.inner {
+            ImageInner::Leap(raw) => unsafe {
+                let ptr = sys::Leap_Image_distortion(&**raw);
+                std::slice::from_raw_parts(ptr, self.distortion_stride() * self.distortion_height())
+            },
+            ImageInner::Owned(buf) => &buf.distortion,
         }
     }
 
     pub fn distortion_width(&self) -> usize {
-        64
+        DISTORTION_WIDTH
     }
 
     /// Returns the number of `f32` elements in each row of the distortion map.
@@ -152,7 +218,200 @@ impl Image {
     }
 
     pub fn distortion_height(&self) -> usize {
-        64
+        DISTORTION_HEIGHT
+    }
+
+    /// Copies this image into an [`ImageBuf`].
+    ///
+    /// Returns an error if the SDK reports an unknown camera.
+    pub fn to_buf(&self) -> Result<ImageBuf, InvalidCameraId> {
+        Ok(match &self.inner {
+            ImageInner::Leap(_) => ImageBuf {
+                sequence_id: self.sequence_id(),
+                camera: self.camera()?,
+                timestamp: self.timestamp(),
+                width: self.width(),
+                height: self.height(),
+                bytes_per_pixel: self.bytes_per_pixel(),
+                data: self.raw_data().to_vec(),
+                distortion: self.raw_distortion().to_vec(),
+            },
+            ImageInner::Owned(buf) => (**buf).clone(),
+        })
+    }
+}
+
+impl Drop for Image {
+    fn drop(&mut self) {
+        if let ImageInner::Leap(raw) = &mut self.inner {
+            // No `Image` destructor, call superclass dtor instead.
+            unsafe {
+                sys::Leap_Interface_Interface_destructor((&mut **raw) as *mut _ as _);
+            }
+        }
+    }
+}
+
+const DISTORTION_WIDTH: usize = 64;
+const DISTORTION_HEIGHT: usize = 64;
+
+/// An owned camera image, which is not tied to the Leap Motion service.
+///
+/// Obtained from [`Image::to_buf`], or constructed from scratch with [`ImageBuf::new`].
+#[derive(Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "ImageBufFields")
+)]
+pub struct ImageBuf {
+    sequence_id: i64,
+    camera: Camera,
+    timestamp: Timestamp,
+    width: usize,
+    height: usize,
+    bytes_per_pixel: usize,
+    /// The pixel data, containing `width * height * bytes_per_pixel` bytes.
+    data: Vec<u8>,
+    /// The distortion map, in the layout returned by [`Image::raw_distortion`], or empty.
+    distortion: Vec<f32>,
+}
+
+impl ImageBuf {
+    /// Creates an image from its pixel data, with a sequence ID and timestamp of 0 and without a
+    /// distortion map.
+    ///
+    /// Returns an error if any of the dimensions is 0, or if `data` does not contain exactly
+    /// `width * height * bytes_per_pixel` bytes.
+    pub fn new(
+        camera: Camera,
+        width: usize,
+        height: usize,
+        bytes_per_pixel: usize,
+        data: Vec<u8>,
+    ) -> Result<Self, InvalidImage> {
+        if width == 0 || height == 0 || bytes_per_pixel == 0 {
+            return Err(InvalidImage("image has no pixels"));
+        }
+        let len = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(bytes_per_pixel));
+        if len != Some(data.len()) {
+            return Err(InvalidImage("pixel data does not match image dimensions"));
+        }
+
+        Ok(Self {
+            sequence_id: 0,
+            camera,
+            timestamp: Timestamp::from_raw(0),
+            width,
+            height,
+            bytes_per_pixel,
+            data,
+            distortion: Vec::new(),
+        })
+    }
+
+    /// Sets the sequence ID of the image.
+    pub fn with_sequence_id(mut self, sequence_id: i64) -> Self {
+        self.sequence_id = sequence_id;
+        self
+    }
+
+    /// Sets the time at which the image was captured.
+    pub fn with_timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the distortion map, in the layout returned by [`Image::raw_distortion`].
+    ///
+    /// Returns an error if `distortion` is neither empty nor a complete distortion map.
+    pub fn with_distortion(mut self, distortion: Vec<f32>) -> Result<Self, InvalidImage> {
+        if !distortion.is_empty() && distortion.len() != DISTORTION_WIDTH * 2 * DISTORTION_HEIGHT {
+            return Err(InvalidImage("incomplete distortion map"));
+        }
+        self.distortion = distortion;
+        Ok(self)
+    }
+
+    pub fn sequence_id(&self) -> i64 {
+        self.sequence_id
+    }
+
+    pub fn camera(&self) -> Camera {
+        self.camera
+    }
+
+    pub fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bytes_per_pixel(&self) -> usize {
+        self.bytes_per_pixel
+    }
+
+    /// Returns the pixel data, containing `width * height * bytes_per_pixel` bytes.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the distortion map, which is empty if the image has none.
+    pub fn distortion(&self) -> &[f32] {
+        &self.distortion
+    }
+}
+
+/// The serialized form of an [`ImageBuf`], validated when deserializing.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ImageBufFields {
+    sequence_id: i64,
+    camera: Camera,
+    timestamp: Timestamp,
+    width: usize,
+    height: usize,
+    bytes_per_pixel: usize,
+    data: Vec<u8>,
+    distortion: Vec<f32>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ImageBufFields> for ImageBuf {
+    type Error = InvalidImage;
+
+    fn try_from(fields: ImageBufFields) -> Result<Self, Self::Error> {
+        ImageBuf::new(
+            fields.camera,
+            fields.width,
+            fields.height,
+            fields.bytes_per_pixel,
+            fields.data,
+        )?
+        .with_sequence_id(fields.sequence_id)
+        .with_timestamp(fields.timestamp)
+        .with_distortion(fields.distortion)
+    }
+}
+
+impl fmt::Debug for ImageBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageBuf")
+            .field("sequence_id", &self.sequence_id)
+            .field("camera", &self.camera)
+            .field("timestamp", &self.timestamp)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("bytes_per_pixel", &self.bytes_per_pixel)
+            .finish_non_exhaustive()
     }
 }
 
@@ -240,6 +499,7 @@ impl fmt::Debug for DistortionDataRow<'_> {
 /// Each entry contains the U/V texture coordinates to use for looking up the corresponding pixels
 /// in the raw camera image. Since the distortion map is smaller than the camera image, the entries
 /// need to be linearly interpolated.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DistortionEntry {
     pub u: f32,
     pub v: f32,
@@ -262,7 +522,45 @@ impl fmt::Debug for DistortionEntry {
 
 /// Identifies one of the cameras on the Leap Motion Controller.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Camera {
     Left,
     Right,
 }
+
+impl TryFrom<i32> for Camera {
+    type Error = InvalidCameraId;
+
+    /// Decodes the camera ID reported by the SDK.
+    fn try_from(id: i32) -> Result<Self, Self::Error> {
+        match id {
+            0 => Ok(Camera::Left),
+            1 => Ok(Camera::Right),
+            _ => Err(InvalidCameraId(id)),
+        }
+    }
+}
+
+/// The error returned when decoding an unknown camera ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidCameraId(pub i32);
+
+impl fmt::Display for InvalidCameraId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid camera ID {}", self.0)
+    }
+}
+
+impl Error for InvalidCameraId {}
+
+/// The error returned when constructing an [`ImageBuf`] whose dimensions do not match its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidImage(&'static str);
+
+impl fmt::Display for InvalidImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid image: {}", self.0)
+    }
+}
+
+impl Error for InvalidImage {}
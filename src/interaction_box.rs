@@ -0,0 +1,69 @@
+use crate::{sys, Vector};
+
+/// The box-shaped region within the field of view of the Leap Motion Controller in which tracking
+/// is most reliable.
+///
+/// The size of the box adapts to the height setting of the Leap Motion service, and to the range of
+/// motion of the tracked hands. Use [`InteractionBox::normalize_point`] to map positions into
+/// application coordinates, eg. to the screen.
+pub struct InteractionBox {
+    inner: Box<sys::Leap_InteractionBox>,
+}
+
+impl InteractionBox {
+    pub(crate) fn from_raw(inner: Box<sys::Leap_InteractionBox>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the center of the box, in millimeters.
+    pub fn center(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_InteractionBox_center(&*self.inner) })
+    }
+
+    /// Returns the size of the box along the X axis, in millimeters.
+    pub fn width(&self) -> f32 {
+        unsafe { sys::Leap_InteractionBox_width(&*self.inner) }
+    }
+
+    /// Returns the size of the box along the Y axis, in millimeters.
+    pub fn height(&self) -> f32 {
+        unsafe { sys::Leap_InteractionBox_height(&*self.inner) }
+    }
+
+    /// Returns the size of the box along the Z axis, in millimeters.
+    pub fn depth(&self) -> f32 {
+        unsafe { sys::Leap_InteractionBox_depth(&*self.inner) }
+    }
+
+    /// Maps a position to the range `[0, 1]` along each axis, with 0 corresponding to the minimum
+    /// and 1 to the maximum coordinate of the box.
+    ///
+    /// If `clamp` is `true`, positions outside of the box are clamped to the range.
+    pub fn normalize_point(&self, position: Vector, clamp: bool) -> Vector {
+        Vector::from_raw(unsafe {
+            sys::Leap_InteractionBox_normalizePoint(&*self.inner, &position.to_raw(), clamp)
+        })
+    }
+
+    /// Performs the inverse of [`InteractionBox::normalize_point`], mapping a normalized position
+    /// back into the Leap Motion coordinate system.
+    pub fn denormalize_point(&self, normalized: Vector) -> Vector {
+        Vector::from_raw(unsafe {
+            sys::Leap_InteractionBox_denormalizePoint(&*self.inner, &normalized.to_raw())
+        })
+    }
+
+    /// Returns whether this interaction box contains valid data.
+    pub(crate) fn is_valid(&self) -> bool {
+        unsafe { sys::Leap_InteractionBox_isValid(&*self.inner) }
+    }
+}
+
+impl Drop for InteractionBox {
+    fn drop(&mut self) {
+        // No `InteractionBox` destructor, call superclass dtor instead.
+        unsafe {
+            sys::Leap_Interface_Interface_destructor((&mut *self.inner) as *mut _ as _);
+        }
+    }
+}
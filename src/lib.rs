@@ -1,45 +1,120 @@
 //! High-level Rust bindings to the legacy Leap Motion C++ SDK 2.3.1.
 //!
 //! (very incomplete, you probably shouldn't be using this)
+//!
+//! # Cargo features
+//!
+//! The `mint`, `glam`, `nalgebra` and `cgmath` features (all disabled by default) add `From`
+//! conversions between [`Vector`]/[`Matrix`] and the corresponding types of those crates.
+//!
+//! The `serde` feature (disabled by default) implements `Serialize` and `Deserialize` for the owned
+//! snapshot types in [`data`] and [`recognize`], the math types, [`Timestamp`], and the plain enums
+//! like [`GestureType`] and [`Policy`].
+//!
+//! The `websocket` feature (disabled by default) adds the `websocket` module, which serves tracking
+//! data using the JSON protocol of the original Leap Motion service, and the `leap-websocket`
+//! binary running it.
+//!
+//! The `osc` feature (disabled by default) adds the `osc` module, which sends tracking data as OSC
+//! messages, and the `leap-osc` binary running it.
+//!
+//! The `midi` feature (disabled by default) adds the `midi` module, which maps hand features to
+//! MIDI messages.
+//!
+//! The `tuio` feature (disabled by default, implies `osc`) adds the `tuio` module, which publishes
+//! fingertips and hands using the TUIO protocol.
 
 // Note: (some?) `Leap.h` types appear to be location-sensitive, so they must be constructed on the
 // heap.
 
+mod backend;
+mod ffi;
+mod list;
 mod listener;
 
+pub mod config;
+pub mod data;
+pub mod device;
+mod error;
+pub mod gesture;
+pub mod hand;
 pub mod image;
+mod interaction_box;
 mod managed;
+mod math;
+#[cfg(feature = "midi")]
+pub mod midi;
+mod mock;
+#[cfg(feature = "osc")]
+pub mod osc;
+pub mod pointable;
+pub mod recognize;
+pub mod recording;
 mod timestamp;
-
+#[cfg(feature = "tuio")]
+pub mod tuio;
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
+use backend::{Backend, VirtualState, HISTORY_LEN};
+use config::Config;
+use device::DeviceList;
+pub use error::Error;
+use gesture::{Gesture, GestureList};
+use hand::{Hand, HandList};
 use image::ImageList;
+pub use interaction_box::InteractionBox;
 pub use managed::ManagedController;
+pub use math::{Matrix, Vector};
+pub use mock::MockController;
+use pointable::{Finger, FingerList, Pointable, PointableList};
 pub use timestamp::Timestamp;
 
-use std::{mem::MaybeUninit, ops::Deref};
+use std::{
+    mem::MaybeUninit,
+    ops::Deref,
+    slice,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
 
 use leapcpp_sys as sys;
 
 use listener::BoxedListener;
-pub use listener::Listener;
+pub use listener::{Listener, ListenerHandle};
 
 /// A connection to a leapd instance.
 ///
 /// This is the main entry point of this library. There is also [`ManagedController`], which
-/// provides additional utilities that are missing from [`Controller`].
+/// provides additional utilities that are missing from [`Controller`], and [`MockController`],
+/// which allows testing code using this library without a Leap Motion device.
 pub struct Controller {
     sys: Box<sys::Leap_Controller>,
+    controller_ref: ControllerRef,
+    // The listeners are registered with the C++ side by address, so they need to stay boxed.
+    #[allow(clippy::vec_box)]
     listeners: Vec<Box<BoxedListener>>,
+    /// Removed listeners that were not notified of their removal in time.
+    #[allow(clippy::vec_box)]
+    exiting: Vec<Box<BoxedListener>>,
 }
 
+/// How long [`Controller::remove_listener`] waits for [`Listener::on_exit`] to be invoked.
+const LISTENER_EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
 impl Controller {
     /// Creates a new [`Controller`], connecting to leapd in the background.
     pub fn new() -> Self {
         unsafe {
             let mut controller = Box::new(MaybeUninit::uninit());
             sys::Leap_Controller_Controller1(controller.as_mut_ptr());
+            let sys = init_box(controller);
             Self {
-                sys: init_box(controller),
+                controller_ref: ControllerRef::from_raw(&*sys),
+                sys,
                 listeners: Vec::new(),
+                exiting: Vec::new(),
             }
         }
     }
@@ -47,17 +122,68 @@ impl Controller {
     /// Adds a new [`Listener`] to the controller, which will be notified of any events.
     ///
     /// The [`Listener`]'s methods will be invoked from another thread, so it has to be thread-safe.
-    pub fn add_listener<L: Listener>(&mut self, listener: L) {
+    ///
+    /// Returns a handle that can be passed to [`Controller::remove_listener`], or
+    /// [`Error::ListenerRegistration`] if the SDK refuses to register the listener, in which case
+    /// the listener is dropped.
+    pub fn add_listener<L: Listener>(&mut self, listener: L) -> Result<ListenerHandle, Error> {
         let mut listener = listener::create_rust_listener(listener);
         let success = unsafe {
             sys::Leap_Controller_addListener(&mut *self.sys, &mut listener.sys as *mut _ as _)
         };
 
-        if success {
-            self.listeners.push(listener);
+        if !success {
+            return Err(Error::ListenerRegistration);
+        }
+
+        let handle = listener.handle;
+        self.listeners.push(listener);
+        Ok(handle)
+    }
+
+    /// Removes a [`Listener`] previously added with [`Controller::add_listener`], and returns it.
+    ///
+    /// This blocks until [`Listener::on_exit`] has been invoked, after which the listener receives
+    /// no more events.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::UnknownListener`] if `handle` does not belong to a listener of this controller,
+    ///   eg. because it was already removed.
+    /// - [`Error::ListenerRemoval`] if the SDK refuses to remove the listener. The listener stays
+    ///   registered.
+    /// - [`Error::ListenerExitTimeout`] if `on_exit` is not invoked within 5 seconds. The listener
+    ///   is removed, but since it might still be invoked, it is only dropped along with the
+    ///   controller.
+    pub fn remove_listener(&mut self, handle: ListenerHandle) -> Result<Box<dyn Listener>, Error> {
+        let index = self
+            .listeners
+            .iter()
+            .position(|listener| listener.handle == handle)
+            .ok_or(Error::UnknownListener)?;
+        let success = unsafe {
+            sys::Leap_Controller_removeListener(
+                &mut *self.sys,
+                &mut self.listeners[index].sys as *mut _ as _,
+            )
+        };
+
+        if !success {
+            return Err(Error::ListenerRemoval);
         }
 
-        // FIXME: should do something when this fails
+        let listener = self.listeners.remove(index);
+        if !listener.wait_for_exit(LISTENER_EXIT_TIMEOUT) {
+            self.exiting.push(listener);
+            return Err(Error::ListenerExitTimeout);
+        }
+        Ok(listener.into_listener())
+    }
+}
+
+impl Default for Controller {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -75,7 +201,54 @@ impl Deref for Controller {
     type Target = ControllerRef;
 
     fn deref(&self) -> &Self::Target {
-        unsafe { ControllerRef::from_raw(&*self.sys) }
+        &self.controller_ref
+    }
+}
+
+/// The operations shared by [`Controller`], [`ManagedController`] and [`MockController`].
+///
+/// This allows writing code that registers [`Listener`]s once, and running it against a real
+/// device as well as in tests:
+///
+/// ```no_run
+/// use leapcpp::{ControllerBackend, Error, Listener, ListenerHandle, Policy};
+///
+/// struct Printer;
+///
+/// impl Listener for Printer {}
+///
+/// fn setup(controller: &mut impl ControllerBackend) -> Result<ListenerHandle, Error> {
+///     controller.controller_ref().set_policy(Policy::BackgroundFrames)?;
+///     controller.add_listener(Printer)
+/// }
+/// ```
+pub trait ControllerBackend {
+    /// Returns the [`ControllerRef`] giving access to the controller's state and tracking data.
+    fn controller_ref(&self) -> &ControllerRef;
+
+    /// Adds a new [`Listener`] to the controller, which will be notified of any events.
+    ///
+    /// See [`Controller::add_listener`].
+    fn add_listener<L: Listener>(&mut self, listener: L) -> Result<ListenerHandle, Error>;
+
+    /// Removes a [`Listener`] previously added with [`ControllerBackend::add_listener`], and
+    /// returns it.
+    ///
+    /// See [`Controller::remove_listener`].
+    fn remove_listener(&mut self, handle: ListenerHandle) -> Result<Box<dyn Listener>, Error>;
+}
+
+impl ControllerBackend for Controller {
+    fn controller_ref(&self) -> &ControllerRef {
+        &self.controller_ref
+    }
+
+    fn add_listener<L: Listener>(&mut self, listener: L) -> Result<ListenerHandle, Error> {
+        Controller::add_listener(self, listener)
+    }
+
+    fn remove_listener(&mut self, handle: ListenerHandle) -> Result<Box<dyn Listener>, Error> {
+        Controller::remove_listener(self, handle)
     }
 }
 
@@ -83,19 +256,32 @@ impl Deref for Controller {
 ///
 /// This exposes most of the controller interface, except access to [`Listener`]s, which requires
 /// extra book-keeping provided by [`Controller`].
-#[repr(transparent)]
+///
+/// The controller is either connected to leapd, replays previously recorded data (see
+/// [`recording`]), or is a [`MockController`].
 pub struct ControllerRef {
-    sys: sys::Leap_Controller,
+    backend: Backend,
 }
 
 impl ControllerRef {
-    unsafe fn from_raw<'a>(sys: *const sys::Leap_Controller) -> &'a Self {
-        &*(sys as *const _ as *const Self)
+    fn from_raw(sys: *const sys::Leap_Controller) -> Self {
+        Self {
+            backend: Backend::Leap(sys),
+        }
+    }
+
+    pub(crate) fn from_virtual(state: Arc<VirtualState>) -> Self {
+        Self {
+            backend: Backend::Virtual(state),
+        }
     }
 
     /// Returns whether the connection to leapd is established.
     pub fn is_service_connected(&self) -> bool {
-        unsafe { sys::Leap_Controller_isServiceConnected(&self.sys) }
+        match &self.backend {
+            Backend::Leap(sys) => unsafe { sys::Leap_Controller_isServiceConnected(*sys) },
+            Backend::Virtual(state) => state.lock().service_connected,
+        }
     }
 
     /// Returns whether a Leap Motion Controller is connected.
@@ -103,7 +289,10 @@ impl ControllerRef {
     /// When a controller is connected or disconnected, [`Listener::on_connect`] or
     /// [`Listener::on_disconnect`] will be invoked.
     pub fn is_connected(&self) -> bool {
-        unsafe { sys::Leap_Controller_isConnected(&self.sys) }
+        match &self.backend {
+            Backend::Leap(sys) => unsafe { sys::Leap_Controller_isConnected(*sys) },
+            Backend::Virtual(state) => state.lock().connected,
+        }
     }
 
     /// Returns whether this application currently has device focus.
@@ -111,7 +300,10 @@ impl ControllerRef {
     /// When this property changes, [`Listener::on_focus_lost`] or [`Listener::on_focus_gained`]
     /// will be invoked.
     pub fn has_focus(&self) -> bool {
-        unsafe { sys::Leap_Controller_hasFocus(&self.sys) }
+        match &self.backend {
+            Backend::Leap(sys) => unsafe { sys::Leap_Controller_hasFocus(*sys) },
+            Backend::Virtual(state) => state.lock().has_focus,
+        }
     }
 
     /// Sets a leapd or device policy.
@@ -120,34 +312,80 @@ impl ControllerRef {
     /// come into effect (eg. your app might not receive any image data even though
     /// [`Policy::Images`] was set).
     ///
-    /// Note that policies are not enabled immediately, so [`ControllerRef::is_policy_set`] might
-    /// still return `false` for a while after a policy is enabled.
-    pub fn set_policy(&self, policy: Policy) {
-        unsafe {
-            sys::Leap_Controller_setPolicy(&self.sys, policy as u32);
+    /// Policies are not enabled immediately, so [`ControllerRef::is_policy_set`] might still return
+    /// `false` for a while after a policy is requested. Use [`ControllerRef::set_policy_and_wait`]
+    /// to wait until leapd applies the policy.
+    ///
+    /// Returns [`Error::ServiceNotConnected`] without requesting the policy if the connection to
+    /// leapd is not established.
+    pub fn set_policy(&self, policy: Policy) -> Result<(), Error> {
+        if !self.is_service_connected() {
+            return Err(Error::ServiceNotConnected);
+        }
+
+        match &self.backend {
+            Backend::Leap(sys) => unsafe { sys::Leap_Controller_setPolicy(*sys, policy as u32) },
+            Backend::Virtual(state) => state.lock().policies |= policy as u32,
         }
+        Ok(())
+    }
+
+    /// Sets a leapd or device policy like [`ControllerRef::set_policy`], and blocks until leapd
+    /// reports the policy as set.
+    ///
+    /// Returns [`Error::PolicyNotApplied`] if that does not happen within `timeout`. Since this
+    /// blocks the calling thread, it should not be called from [`Listener`] methods, which would
+    /// stall the delivery of events.
+    pub fn set_policy_and_wait(&self, policy: Policy, timeout: Duration) -> Result<(), Error> {
+        self.set_policy(policy)?;
+
+        let deadline = Instant::now() + timeout;
+        while !self.is_policy_set(policy) {
+            if Instant::now() >= deadline {
+                return Err(Error::PolicyNotApplied(policy));
+            }
+            thread::sleep(POLICY_POLL_INTERVAL);
+        }
+        Ok(())
     }
 
     /// Unsets a leapd or device policy.
-    pub fn clear_policy(&self, policy: Policy) {
-        unsafe {
-            sys::Leap_Controller_clearPolicy(&self.sys, policy as u32);
+    ///
+    /// Returns [`Error::ServiceNotConnected`] without unsetting the policy if the connection to
+    /// leapd is not established.
+    pub fn clear_policy(&self, policy: Policy) -> Result<(), Error> {
+        if !self.is_service_connected() {
+            return Err(Error::ServiceNotConnected);
+        }
+
+        match &self.backend {
+            Backend::Leap(sys) => unsafe { sys::Leap_Controller_clearPolicy(*sys, policy as u32) },
+            Backend::Virtual(state) => state.lock().policies &= !(policy as u32),
         }
+        Ok(())
     }
 
     /// Returns whether a leapd or device policy is currently enabled.
     pub fn is_policy_set(&self, policy: Policy) -> bool {
-        unsafe { sys::Leap_Controller_isPolicySet(&self.sys, policy as u32) }
+        match &self.backend {
+            Backend::Leap(sys) => unsafe { sys::Leap_Controller_isPolicySet(*sys, policy as u32) },
+            Backend::Virtual(state) => state.lock().policies & policy as u32 != 0,
+        }
     }
 
     /// Returns the current timestamp.
     pub fn now(&self) -> Timestamp {
-        let raw = unsafe { sys::Leap_Controller_now(&self.sys) };
+        let raw = match &self.backend {
+            Backend::Leap(sys) => unsafe { sys::Leap_Controller_now(*sys) },
+            Backend::Virtual(state) => state.lock().now,
+        };
         Timestamp::from_raw(raw)
     }
 
     /// Returns the most recent frame of tracking data.
-    pub fn frame(&self) -> Frame {
+    ///
+    /// Returns `None` if no tracking data has been received yet.
+    pub fn frame(&self) -> Option<Frame> {
         self.frame_at(0)
     }
 
@@ -156,55 +394,145 @@ impl ControllerRef {
     /// `history` specified how old the returned frame should be. 0 selects the most recent frame, 1
     /// the frame before that, and so on.
     ///
-    /// The maximum value of `history` is 59. Using a value greater than that will return an invalid
-    /// [`Frame`] object.
-    pub fn frame_at(&self, history: u8) -> Frame {
-        unsafe {
-            let mut frame = Box::new(MaybeUninit::uninit());
-            sys::Leap_Controller_frame(frame.as_mut_ptr(), &self.sys, history.into());
-            Frame {
-                inner: init_box(frame),
+    /// Returns `None` if no frame of that age is available. The SDK keeps 60 frames, so the maximum
+    /// value of `history` is 59.
+    ///
+    /// Frames injected with [`MockController::push_frame_data`] are not backed by the SDK, so this
+    /// returns `None` for them. [`ControllerRef::frame_data_at`] returns all frames.
+    pub fn frame_at(&self, history: u8) -> Option<Frame> {
+        if usize::from(history) >= HISTORY_LEN {
+            return None;
+        }
+
+        match &self.backend {
+            Backend::Leap(sys) => {
+                let frame = unsafe {
+                    let mut frame = Box::new(MaybeUninit::uninit());
+                    sys::Leap_Controller_frame(frame.as_mut_ptr(), *sys, history.into());
+                    Frame {
+                        inner: init_box(frame),
+                    }
+                };
+                frame.is_valid().then_some(frame)
+            }
+            Backend::Virtual(state) => state.lock().frame(history.into()),
+        }
+    }
+
+    /// Returns an owned copy of the most recent frame of tracking data.
+    ///
+    /// Returns `None` if no tracking data has been received yet.
+    pub fn frame_data(&self) -> Option<data::FrameData> {
+        self.frame_data_at(0)
+    }
+
+    /// Returns an owned copy of a frame of tracking data, of the specified age.
+    ///
+    /// Like [`ControllerRef::frame_at`], but also returns the frames injected with
+    /// [`MockController::push_frame_data`]. The motion factors of frames obtained from the SDK are
+    /// computed relative to the frame before them.
+    pub fn frame_data_at(&self, history: u8) -> Option<data::FrameData> {
+        match &self.backend {
+            Backend::Leap(_) => {
+                let frame = self.frame_at(history)?;
+                let since = history.checked_add(1).and_then(|h| self.frame_at(h));
+                Some(data::FrameData::from_frames(&frame, since.as_ref()))
+            }
+            Backend::Virtual(state) => {
+                if usize::from(history) >= HISTORY_LEN {
+                    return None;
+                }
+                state.lock().frame_data(history.into())
             }
         }
     }
 
     /// Returns the most recent set of captured images.
     pub fn images(&self) -> ImageList {
+        match &self.backend {
+            Backend::Leap(sys) => unsafe {
+                let mut images = Box::new(MaybeUninit::uninit());
+                sys::Leap_Controller_images(images.as_mut_ptr(), *sys);
+                ImageList::from_raw(init_box(images))
+            },
+            Backend::Virtual(state) => ImageList::from_bufs(state.lock().images.clone()),
+        }
+    }
+
+    /// Returns the configuration of the Leap Motion service.
+    ///
+    /// When replaying a recording, the returned [`Config`] is not connected to the service: it
+    /// contains no values and cannot be saved.
+    pub fn config(&self) -> Config {
         unsafe {
-            let mut images = Box::new(MaybeUninit::uninit());
-            sys::Leap_Controller_images(images.as_mut_ptr(), &self.sys);
-            ImageList::from_raw(init_box(images))
+            let mut config = Box::new(MaybeUninit::uninit());
+            match &self.backend {
+                Backend::Leap(sys) => sys::Leap_Controller_config(config.as_mut_ptr(), *sys),
+                Backend::Virtual(_) => sys::Leap_Config_Config(config.as_mut_ptr()),
+            }
+            Config::from_raw(init_box(config))
+        }
+    }
+
+    /// Returns the Leap Motion devices currently attached to the system.
+    ///
+    /// When replaying a recording, the returned list is empty.
+    pub fn devices(&self) -> DeviceList {
+        unsafe {
+            let mut devices = Box::new(MaybeUninit::uninit());
+            match &self.backend {
+                Backend::Leap(sys) => sys::Leap_Controller_devices(devices.as_mut_ptr(), *sys),
+                Backend::Virtual(_) => sys::Leap_DeviceList_DeviceList1(devices.as_mut_ptr()),
+            }
+            DeviceList::from_raw(init_box(devices))
         }
     }
 
     /// Enables detection and reporting of a gesture.
     ///
-    /// Enabled gestures will be included in the [`Frame`] objects.
+    /// Enabled gestures will be included in the [`Frame`] objects. Their recognition parameters can
+    /// be tuned via [`Config::set_gesture_setting`].
     pub fn enable_gesture(&self, gesture: GestureType) {
-        unsafe {
-            sys::Leap_Controller_enableGesture(&self.sys, gesture as i32, true);
+        match &self.backend {
+            Backend::Leap(sys) => unsafe {
+                sys::Leap_Controller_enableGesture(*sys, gesture as i32, true);
+            },
+            Backend::Virtual(state) => state.lock().gestures |= 1 << gesture as i32,
         }
     }
 
     /// Disables detection and reporting of a gesture.
     pub fn disable_gesture(&self, gesture: GestureType) {
-        unsafe {
-            sys::Leap_Controller_enableGesture(&self.sys, gesture as i32, false);
+        match &self.backend {
+            Backend::Leap(sys) => unsafe {
+                sys::Leap_Controller_enableGesture(*sys, gesture as i32, false);
+            },
+            Backend::Virtual(state) => state.lock().gestures &= !(1 << gesture as i32),
         }
     }
 
     /// Returns whether detection and reporting of a gesture is enabled.
     pub fn is_gesture_enabled(&self, gesture: GestureType) -> bool {
-        unsafe { sys::Leap_Controller_isGestureEnabled(&self.sys, gesture as i32) }
+        match &self.backend {
+            Backend::Leap(sys) => unsafe {
+                sys::Leap_Controller_isGestureEnabled(*sys, gesture as i32)
+            },
+            Backend::Virtual(state) => state.lock().gestures & (1 << gesture as i32) != 0,
+        }
     }
 }
 
+/// How often [`ControllerRef::set_policy_and_wait`] checks whether leapd applied the policy.
+const POLICY_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 /// A device or leapd policy.
 ///
 /// These can be enabled or disabled via [`ControllerRef::set_policy`] and
 /// [`ControllerRef::clear_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u32)]
 #[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Policy {
     //Default = sys::Leap_Controller_PolicyFlag_POLICY_DEFAULT,
     /// Receive [`Frame`]s even when the application does not have focus
@@ -220,13 +548,24 @@ pub enum Policy {
 }
 
 /// A frame of tracking data.
-///
-/// (note that this doesn't currently let you access the interesting tracking data)
 pub struct Frame {
     inner: Box<sys::Leap_Frame>,
 }
 
 impl Frame {
+    /// Returns an invalid frame, as the SDK does when no data is available.
+    ///
+    /// Never handed out by the public API.
+    fn invalid() -> Self {
+        unsafe {
+            let mut frame = Box::new(MaybeUninit::uninit());
+            sys::Leap_Frame_Frame1(frame.as_mut_ptr());
+            Frame {
+                inner: init_box(frame),
+            }
+        }
+    }
+
     /// Returns the frame's unique ID.
     ///
     /// The ID is incremented for every reported frame.
@@ -245,10 +584,233 @@ impl Frame {
         unsafe { sys::Leap_Frame_currentFramesPerSecond(&*self.inner) }
     }
 
+    /// Returns the list of hands detected in this frame.
+    pub fn hands(&self) -> HandList {
+        unsafe {
+            let mut hands = Box::new(MaybeUninit::uninit());
+            sys::Leap_Frame_hands(hands.as_mut_ptr(), &*self.inner);
+            HandList::from_raw(init_box(hands))
+        }
+    }
+
+    /// Returns the list of fingers detected in this frame.
+    pub fn fingers(&self) -> FingerList {
+        unsafe {
+            let mut fingers = Box::new(MaybeUninit::uninit());
+            sys::Leap_Frame_fingers(fingers.as_mut_ptr(), &*self.inner);
+            FingerList::from_raw(init_box(fingers))
+        }
+    }
+
+    /// Returns the list of fingers and tools detected in this frame.
+    pub fn pointables(&self) -> PointableList {
+        unsafe {
+            let mut pointables = Box::new(MaybeUninit::uninit());
+            sys::Leap_Frame_pointables(pointables.as_mut_ptr(), &*self.inner);
+            PointableList::from_raw(init_box(pointables))
+        }
+    }
+
+    /// Returns the hand with the given ID, if it is part of this frame.
+    ///
+    /// Hand IDs stay the same while a hand is tracked, so this can be used to follow a hand through
+    /// the frames returned by [`ControllerRef::frame_at`].
+    pub fn hand(&self, id: i32) -> Option<Hand> {
+        let hand = unsafe {
+            let mut hand = Box::new(MaybeUninit::uninit());
+            sys::Leap_Frame_hand(hand.as_mut_ptr(), &*self.inner, id);
+            Hand::from_raw(init_box(hand))
+        };
+        hand.is_valid().then_some(hand)
+    }
+
+    /// Returns the finger with the given ID, if it is part of this frame.
+    pub fn finger(&self, id: i32) -> Option<Finger> {
+        let finger = unsafe {
+            let mut finger = Box::new(MaybeUninit::uninit());
+            sys::Leap_Frame_finger(finger.as_mut_ptr(), &*self.inner, id);
+            Finger::from_raw(init_box(finger))
+        };
+        finger.is_valid().then_some(finger)
+    }
+
+    /// Returns the finger or tool with the given ID, if it is part of this frame.
+    pub fn pointable(&self, id: i32) -> Option<Pointable> {
+        let pointable = unsafe {
+            let mut pointable = Box::new(MaybeUninit::uninit());
+            sys::Leap_Frame_pointable(pointable.as_mut_ptr(), &*self.inner, id);
+            Pointable::from_raw(init_box(pointable))
+        };
+        pointable.is_valid().then_some(pointable)
+    }
+
+    /// Returns the gesture with the given ID, if it was updated in this frame.
+    pub fn gesture(&self, id: i32) -> Option<Gesture> {
+        let gesture = unsafe {
+            let mut gesture = Box::new(MaybeUninit::uninit());
+            sys::Leap_Frame_gesture(gesture.as_mut_ptr(), &*self.inner, id);
+            Gesture::from_raw(init_box(gesture))
+        };
+        gesture.is_valid().then_some(gesture)
+    }
+
+    /// Returns the gestures that were updated in this frame.
+    ///
+    /// Only gestures enabled via [`ControllerRef::enable_gesture`] are reported.
+    pub fn gestures(&self) -> GestureList {
+        unsafe {
+            let mut gestures = Box::new(MaybeUninit::uninit());
+            sys::Leap_Frame_gestures(gestures.as_mut_ptr(), &*self.inner);
+            GestureList::from_raw(init_box(gestures))
+        }
+    }
+
+    /// Returns all gestures that were updated between `since` and this frame.
+    ///
+    /// This is useful when frames might have been skipped, since gestures are only reported in the
+    /// frame in which their state changes.
+    pub fn gestures_since(&self, since: &Frame) -> GestureList {
+        unsafe {
+            let mut gestures = Box::new(MaybeUninit::uninit());
+            sys::Leap_Frame_gestures1(gestures.as_mut_ptr(), &*self.inner, &*since.inner);
+            GestureList::from_raw(init_box(gestures))
+        }
+    }
+
+    /// Returns the change of position of the tracked objects between `since` and this frame, in
+    /// millimeters.
+    ///
+    /// The motion factors of a frame describe the apparent motion of all hands and fingers in it,
+    /// as if they were a single object. `since` is usually obtained from
+    /// [`ControllerRef::frame_at`].
+    pub fn translation(&self, since: &Frame) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Frame_translation(&*self.inner, &*since.inner) })
+    }
+
+    /// Returns the estimated probability, from 0 to 1, that the motion between `since` and this
+    /// frame is intended as a translating motion.
+    pub fn translation_probability(&self, since: &Frame) -> f32 {
+        unsafe { sys::Leap_Frame_translationProbability(&*self.inner, &*since.inner) }
+    }
+
+    /// Returns the axis of the rotation of the tracked objects between `since` and this frame.
+    pub fn rotation_axis(&self, since: &Frame) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Frame_rotationAxis(&*self.inner, &*since.inner) })
+    }
+
+    /// Returns the angle of the rotation of the tracked objects around [`Frame::rotation_axis`]
+    /// between `since` and this frame, in radians.
+    pub fn rotation_angle(&self, since: &Frame) -> f32 {
+        unsafe { sys::Leap_Frame_rotationAngle(&*self.inner, &*since.inner) }
+    }
+
+    /// Returns the angle of the rotation of the tracked objects around `axis` between `since` and
+    /// this frame, in radians.
+    pub fn rotation_angle_around(&self, since: &Frame, axis: Vector) -> f32 {
+        unsafe { sys::Leap_Frame_rotationAngle1(&*self.inner, &*since.inner, &axis.to_raw()) }
+    }
+
+    /// Returns the rotation of the tracked objects between `since` and this frame, as a
+    /// transformation matrix.
+    pub fn rotation_matrix(&self, since: &Frame) -> Matrix {
+        Matrix::from_raw(unsafe { sys::Leap_Frame_rotationMatrix(&*self.inner, &*since.inner) })
+    }
+
+    /// Returns the estimated probability, from 0 to 1, that the motion between `since` and this
+    /// frame is intended as a rotating motion.
+    pub fn rotation_probability(&self, since: &Frame) -> f32 {
+        unsafe { sys::Leap_Frame_rotationProbability(&*self.inner, &*since.inner) }
+    }
+
+    /// Returns the scaling of the tracked objects between `since` and this frame.
+    ///
+    /// Values above 1 indicate expansion, values below 1 contraction.
+    pub fn scale_factor(&self, since: &Frame) -> f32 {
+        unsafe { sys::Leap_Frame_scaleFactor(&*self.inner, &*since.inner) }
+    }
+
+    /// Returns the estimated probability, from 0 to 1, that the motion between `since` and this
+    /// frame is intended as a scaling motion.
+    pub fn scale_probability(&self, since: &Frame) -> f32 {
+        unsafe { sys::Leap_Frame_scaleProbability(&*self.inner, &*since.inner) }
+    }
+
+    /// Returns the current [`InteractionBox`] of the Leap Motion service, if this frame has one.
+    pub fn interaction_box(&self) -> Option<InteractionBox> {
+        let interaction_box = unsafe {
+            let mut interaction_box = Box::new(MaybeUninit::uninit());
+            sys::Leap_Frame_interactionBox(interaction_box.as_mut_ptr(), &*self.inner);
+            InteractionBox::from_raw(init_box(interaction_box))
+        };
+        interaction_box.is_valid().then_some(interaction_box)
+    }
+
     /// Returns whether this frame contains valid data.
-    pub fn is_valid(&self) -> bool {
+    pub(crate) fn is_valid(&self) -> bool {
         unsafe { sys::Leap_Frame_isValid(&*self.inner) }
     }
+
+    /// Copies the tracking data in this frame into an owned [`FrameData`][data::FrameData].
+    ///
+    /// The copy has no motion factors, see [`Frame::to_data_since`].
+    pub fn to_data(&self) -> data::FrameData {
+        self.into()
+    }
+
+    /// Like [`Frame::to_data`], but also copies the motion factors of the frame and its hands
+    /// relative to `since`, usually the previous frame.
+    pub fn to_data_since(&self, since: &Frame) -> data::FrameData {
+        data::FrameData::from_frames(self, Some(since))
+    }
+
+    /// Encodes this frame in the SDK's binary serialization format.
+    ///
+    /// The result can be turned back into a [`Frame`] with [`Frame::deserialize`].
+    pub fn serialize(&self) -> Vec<u8> {
+        unsafe {
+            let mut len = 0;
+            let data = ffi::Leap_Frame_serializeCString(&*self.inner, &mut len);
+            let bytes = slice::from_raw_parts(data as *const u8, len).to_vec();
+            sys::Leap_Interface_deleteCString(data);
+            bytes
+        }
+    }
+
+    /// Decodes a frame previously encoded with [`Frame::serialize`].
+    ///
+    /// The SDK can only decode frames while a [`Controller`] exists. Methods relating a frame to an
+    /// earlier one, like [`Frame::translation`] or [`Frame::gestures_since`], require the frames to
+    /// be decoded in the order they were captured.
+    ///
+    /// Returns [`Error::Deserialize`] if `data` is empty, and [`Error::InvalidFrame`] if the SDK
+    /// does not decode a valid frame from it.
+    pub fn deserialize(data: &[u8]) -> Result<Frame, Error> {
+        if data.is_empty() {
+            return Err(Error::Deserialize);
+        }
+
+        let mut frame = Frame::invalid();
+        unsafe {
+            ffi::Leap_Frame_deserializeCString(&mut *frame.inner, data.as_ptr() as _, data.len());
+        }
+
+        if frame.is_valid() {
+            Ok(frame)
+        } else {
+            Err(Error::InvalidFrame)
+        }
+    }
+
+    /// Returns another handle to the same SDK frame.
+    pub(crate) fn copy(&self) -> Frame {
+        unsafe {
+            let mut frame = Box::new(MaybeUninit::<sys::Leap_Frame>::uninit());
+            sys::Leap_Interface_Interface2(frame.as_mut_ptr() as *mut _, &self.inner._base);
+            Frame {
+                inner: init_box(frame),
+            }
+        }
+    }
 }
 
 impl Drop for Frame {
@@ -266,8 +828,10 @@ unsafe fn init_box<T>(bx: Box<MaybeUninit<T>>) -> Box<T> {
 }
 
 /// Types of gestures the Leap Motion service can detect and report to the application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(i32)]
 #[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GestureType {
     //Invalid = sys::Leap_Gesture_Type_TYPE_INVALID,
     /// Horizontal swiping movement of a hand, with fingers extended.
@@ -283,9 +847,26 @@ pub enum GestureType {
     KeyTap = sys::Leap_Gesture_Type_TYPE_KEY_TAP,
 }
 
+impl GestureType {
+    /// All gesture types, eg. for enabling every gesture.
+    pub const ALL: [GestureType; 4] = [Self::Swipe, Self::Circle, Self::ScreenTap, Self::KeyTap];
+
+    fn from_raw(raw: sys::Leap_Gesture_Type) -> Option<Self> {
+        match raw {
+            sys::Leap_Gesture_Type_TYPE_SWIPE => Some(Self::Swipe),
+            sys::Leap_Gesture_Type_TYPE_CIRCLE => Some(Self::Circle),
+            sys::Leap_Gesture_Type_TYPE_SCREEN_TAP => Some(Self::ScreenTap),
+            sys::Leap_Gesture_Type_TYPE_KEY_TAP => Some(Self::KeyTap),
+            _ => None,
+        }
+    }
+}
+
 /// States describing the progression of a gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(i32)]
 #[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GestureState {
     //Invalid = sys::Leap_Gesture_State_STATE_INVALID,
     /// Gesture is starting just now.
@@ -297,3 +878,14 @@ pub enum GestureState {
     /// Gesture that was started earlier is ending.
     Stop = sys::Leap_Gesture_State_STATE_STOP,
 }
+
+impl GestureState {
+    fn from_raw(raw: sys::Leap_Gesture_State) -> Option<Self> {
+        match raw {
+            sys::Leap_Gesture_State_STATE_START => Some(Self::Start),
+            sys::Leap_Gesture_State_STATE_UPDATE => Some(Self::Update),
+            sys::Leap_Gesture_State_STATE_STOP => Some(Self::Stop),
+            _ => None,
+        }
+    }
+}
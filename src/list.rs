@@ -0,0 +1,82 @@
+/// Defines a wrapper around one of the SDK's list types, and an iterator over its elements.
+///
+/// The element type has to provide a `from_raw` constructor taking the boxed SDK object.
+macro_rules! ffi_list {
+    (
+        $(#[$list_attr:meta])*
+        pub struct $list:ident($raw:ty) {
+            count: $count:path,
+            index: $index:path,
+        }
+
+        $(#[$iter_attr:meta])*
+        pub struct $iter:ident -> $item:ident;
+    ) => {
+        $(#[$list_attr])*
+        pub struct $list {
+            raw: Box<$raw>,
+        }
+
+        impl $list {
+            pub(crate) fn from_raw(raw: Box<$raw>) -> Self {
+                Self { raw }
+            }
+
+            /// Returns the number of elements in the list.
+            pub fn len(&self) -> usize {
+                unsafe { $count(&*self.raw) as usize }
+            }
+
+            /// Returns whether this list is empty.
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// Returns an iterator over the elements in the list.
+            pub fn iter(&self) -> $iter<'_> {
+                $iter {
+                    list: self,
+                    next: 0,
+                    len: self.len(),
+                }
+            }
+        }
+
+        impl Drop for $list {
+            fn drop(&mut self) {
+                // No destructors for the list types, call superclass dtor instead.
+                unsafe {
+                    $crate::sys::Leap_Interface_Interface_destructor(
+                        (&mut *self.raw) as *mut _ as _,
+                    );
+                }
+            }
+        }
+
+        $(#[$iter_attr])*
+        pub struct $iter<'a> {
+            list: &'a $list,
+            next: usize,
+            len: usize,
+        }
+
+        impl<'a> Iterator for $iter<'a> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.next == self.len {
+                    None
+                } else {
+                    unsafe {
+                        let mut item = Box::new(std::mem::MaybeUninit::uninit());
+                        $index(item.as_mut_ptr(), &*self.list.raw, self.next as i32);
+                        self.next += 1;
+                        Some($item::from_raw($crate::init_box(item)))
+                    }
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use ffi_list;
@@ -3,6 +3,11 @@ use std::{
     mem::MaybeUninit,
     panic::{catch_unwind, AssertUnwindSafe},
     process,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Condvar, Mutex,
+    },
+    time::Duration,
 };
 
 use crate::{sys, ControllerRef};
@@ -28,14 +33,65 @@ pub trait Listener: Send + 'static {
     fn on_images(&mut self, controller: &ControllerRef) {}
 }
 
+/// Identifies a [`Listener`] added to a [`Controller`][crate::Controller].
+///
+/// Returned by [`Controller::add_listener`][crate::Controller::add_listener], and used to remove
+/// the listener again with [`Controller::remove_listener`][crate::Controller::remove_listener].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerHandle(u64);
+
+impl ListenerHandle {
+    /// Returns a handle that is distinct from all previously returned ones.
+    pub(crate) fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
 pub(crate) struct BoxedListener {
-    #[allow(dead_code)] // needed for drop side-effect
+    pub(crate) handle: ListenerHandle,
     rust: Box<dyn Listener>,
+    userdata: Box<Userdata>,
     pub(crate) sys: sys::Leap_RustListener,
 }
 
+impl BoxedListener {
+    /// Blocks until [`Listener::on_exit`] has been invoked, or until `timeout` elapses.
+    ///
+    /// Returns whether `on_exit` has been invoked.
+    pub(crate) fn wait_for_exit(&self, timeout: Duration) -> bool {
+        let exited = self.userdata.exited.lock().unwrap();
+        let (exited, _) = self
+            .userdata
+            .exit
+            .wait_timeout_while(exited, timeout, |exited| !*exited)
+            .unwrap();
+        *exited
+    }
+
+    /// Returns the Rust listener.
+    ///
+    /// Must only be called once the listener will not be invoked anymore.
+    pub(crate) fn into_listener(self) -> Box<dyn Listener> {
+        self.rust
+    }
+}
+
+/// The data the callbacks receive through their `userdata` pointer.
+struct Userdata {
+    /// Points to the `L` passed to `create_rust_listener`.
+    listener: *mut c_void,
+    exited: Mutex<bool>,
+    exit: Condvar,
+}
+
 pub(crate) fn create_rust_listener<L: Listener>(listener: L) -> Box<BoxedListener> {
-    let boxed = Box::new(listener);
+    let mut boxed = Box::new(listener);
+    let userdata = Box::new(Userdata {
+        listener: &mut *boxed as *mut L as _,
+        exited: Mutex::new(false),
+        exit: Condvar::new(),
+    });
     let callbacks = sys::Leap_RustListenerCallbacks {
         onInit: Some(cb_on_init::<L>),
         onConnect: Some(cb_on_connect::<L>),
@@ -48,7 +104,7 @@ pub(crate) fn create_rust_listener<L: Listener>(listener: L) -> Box<BoxedListene
         onServiceDisconnect: Some(cb_on_service_disconnect::<L>),
         onDeviceChange: Some(cb_on_device_change::<L>),
         onImages: Some(cb_on_images::<L>),
-        userdata: (&*boxed) as *const _ as _,
+        userdata: &*userdata as *const Userdata as _,
     };
 
     unsafe {
@@ -56,7 +112,9 @@ pub(crate) fn create_rust_listener<L: Listener>(listener: L) -> Box<BoxedListene
         sys::Leap_RustListener_RustListener(sys.as_mut_ptr(), callbacks);
 
         Box::new(BoxedListener {
+            handle: ListenerHandle::next(),
             rust: boxed,
+            userdata,
             sys: sys.assume_init(),
         })
     }
@@ -73,26 +131,46 @@ macro_rules! wrap_callbacks {
                 userdata: *mut c_void,
                 controller: *const sys::Leap_Controller,
             ) {
-                let listener = &mut *(userdata as *mut L);
-                let controller = ControllerRef::from_raw(controller);
-
-                let res = catch_unwind(AssertUnwindSafe(|| {
-                    listener.$method_name(controller);
-                }));
-
-                if res.is_err() {
-                    process::abort();
-                }
+                dispatch(userdata, controller, L::$method_name);
             }
         )+
     };
 }
 
+unsafe fn dispatch<L: Listener>(
+    userdata: *mut c_void,
+    controller: *const sys::Leap_Controller,
+    method: fn(&mut L, &ControllerRef),
+) {
+    let userdata = &*(userdata as *const Userdata);
+    let listener = &mut *(userdata.listener as *mut L);
+    let controller = ControllerRef::from_raw(controller);
+
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        method(listener, &controller);
+    }));
+
+    if res.is_err() {
+        process::abort();
+    }
+}
+
+/// Like the wrappers below, but also wakes up [`BoxedListener::wait_for_exit`].
+unsafe extern "C" fn cb_on_exit<L: Listener>(
+    userdata: *mut c_void,
+    controller: *const sys::Leap_Controller,
+) {
+    dispatch(userdata, controller, L::on_exit);
+
+    let userdata = &*(userdata as *const Userdata);
+    *userdata.exited.lock().unwrap() = true;
+    userdata.exit.notify_all();
+}
+
 wrap_callbacks! {
     cb_on_init -> on_init,
     cb_on_connect -> on_connect,
     cb_on_disconnect -> on_disconnect,
-    cb_on_exit -> on_exit,
     cb_on_frame -> on_frame,
     cb_on_focus_gained -> on_focus_gained,
     cb_on_focus_lost -> on_focus_lost,
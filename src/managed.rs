@@ -3,7 +3,7 @@ use std::{
     sync::{Arc, Condvar, Mutex},
 };
 
-use crate::{Controller, ControllerRef, Listener};
+use crate::{Controller, ControllerBackend, ControllerRef, Error, Listener, ListenerHandle};
 
 /// A [`Controller`] that adds a few convenience methods to perform blocking waits for events.
 pub struct ManagedController {
@@ -36,9 +36,12 @@ impl ManagedController {
         });
 
         let mut inner = Controller::new();
-        inner.add_listener(ManagedListener {
-            shared: shared.clone(),
-        });
+        // The SDK only refuses listeners that are already registered, which this one can't be.
+        inner
+            .add_listener(ManagedListener {
+                shared: shared.clone(),
+            })
+            .expect("failed to register internal listener");
 
         Self { inner, shared }
     }
@@ -82,6 +85,8 @@ impl ManagedController {
     /// - A Leap Motion device is plugged in or removed.
     /// - "Robust" mode is enabled or disabled.
     /// - The image capture rate is changed.
+    ///
+    /// Use [`ControllerRef::devices`] to inspect the new device configuration.
     pub fn wait_until_device_change(&self) {
         self.wait_until_counter(&self.shared.device_change, &self.shared.mutex_device_change);
     }
@@ -110,6 +115,26 @@ impl ManagedController {
     }
 }
 
+impl Default for ManagedController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ControllerBackend for ManagedController {
+    fn controller_ref(&self) -> &ControllerRef {
+        &self.inner
+    }
+
+    fn add_listener<L: Listener>(&mut self, listener: L) -> Result<ListenerHandle, Error> {
+        self.inner.add_listener(listener)
+    }
+
+    fn remove_listener(&mut self, handle: ListenerHandle) -> Result<Box<dyn Listener>, Error> {
+        self.inner.remove_listener(handle)
+    }
+}
+
 impl Deref for ManagedController {
     type Target = Controller;
 
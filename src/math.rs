@@ -0,0 +1,511 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Index, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use crate::sys;
+
+#[cfg(feature = "cgmath")]
+mod cgmath;
+#[cfg(feature = "glam")]
+mod glam;
+#[cfg(feature = "mint")]
+mod mint;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+
+/// A 3-component vector, used for positions, directions and velocities.
+///
+/// Mirrors `Leap::Vector`. Positions are given in millimeters, in the Leap Motion coordinate
+/// system: the origin is at the center of the device, +Y points up, +Z points towards the user.
+#[derive(Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vector {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Vector {
+    /// The zero vector.
+    pub const ZERO: Self = Self::new(0.0, 0.0, 0.0);
+    /// The unit vector pointing along the positive X axis.
+    pub const X_AXIS: Self = Self::new(1.0, 0.0, 0.0);
+    /// The unit vector pointing along the positive Y axis.
+    pub const Y_AXIS: Self = Self::new(0.0, 1.0, 0.0);
+    /// The unit vector pointing along the positive Z axis.
+    pub const Z_AXIS: Self = Self::new(0.0, 0.0, 1.0);
+    /// The unit vector pointing forward, away from the user (along the negative Z axis).
+    pub const FORWARD: Self = Self::new(0.0, 0.0, -1.0);
+    /// The unit vector pointing backward, towards the user (along the positive Z axis).
+    pub const BACKWARD: Self = Self::new(0.0, 0.0, 1.0);
+    /// The unit vector pointing left (along the negative X axis).
+    pub const LEFT: Self = Self::new(-1.0, 0.0, 0.0);
+    /// The unit vector pointing right (along the positive X axis).
+    pub const RIGHT: Self = Self::new(1.0, 0.0, 0.0);
+    /// The unit vector pointing up (along the positive Y axis).
+    pub const UP: Self = Self::new(0.0, 1.0, 0.0);
+    /// The unit vector pointing down (along the negative Y axis).
+    pub const DOWN: Self = Self::new(0.0, -1.0, 0.0);
+
+    /// Creates a vector from its components.
+    #[inline]
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    #[inline]
+    pub(crate) fn from_raw(raw: sys::Leap_Vector) -> Self {
+        Self::new(raw.x, raw.y, raw.z)
+    }
+
+    #[inline]
+    pub(crate) fn to_raw(self) -> sys::Leap_Vector {
+        sys::Leap_Vector {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+
+    /// Returns the length of this vector.
+    pub fn magnitude(self) -> f32 {
+        self.magnitude_squared().sqrt()
+    }
+
+    /// Returns the squared length of this vector.
+    pub fn magnitude_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    /// Returns the distance between the points represented by `self` and `other`.
+    pub fn distance_to(self, other: Vector) -> f32 {
+        (self - other).magnitude()
+    }
+
+    /// Returns the angle between `self` and `other`, in radians.
+    ///
+    /// If either vector has zero length, 0 is returned.
+    pub fn angle_to(self, other: Vector) -> f32 {
+        let denom = self.magnitude_squared() * other.magnitude_squared();
+        if denom <= sys::Leap_EPSILON {
+            return 0.0;
+        }
+        let val = self.dot(other) / denom.sqrt();
+        val.clamp(-1.0, 1.0).acos()
+    }
+
+    /// Returns the pitch angle in radians.
+    ///
+    /// Pitch is the angle between the negative Z axis and the projection of the vector onto the
+    /// Y-Z plane. It is positive when the vector points upwards.
+    pub fn pitch(self) -> f32 {
+        self.y.atan2(-self.z)
+    }
+
+    /// Returns the yaw angle in radians.
+    ///
+    /// Yaw is the angle between the negative Z axis and the projection of the vector onto the X-Z
+    /// plane. It is positive when the vector points to the right.
+    pub fn yaw(self) -> f32 {
+        self.x.atan2(-self.z)
+    }
+
+    /// Returns the roll angle in radians.
+    ///
+    /// Roll is the angle between the negative Y axis and the projection of the vector onto the X-Y
+    /// plane. It is positive when the vector points to the right, which makes it useful when called
+    /// on [`Hand::palm_normal`][crate::hand::Hand::palm_normal].
+    pub fn roll(self) -> f32 {
+        self.x.atan2(-self.y)
+    }
+
+    /// Returns the dot product of `self` and `other`.
+    pub fn dot(self, other: Vector) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Returns the cross product of `self` and `other`.
+    pub fn cross(self, other: Vector) -> Vector {
+        Vector::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Returns a unit vector pointing in the same direction as `self`.
+    ///
+    /// If `self` has zero length, the zero vector is returned.
+    pub fn normalized(self) -> Vector {
+        let denom = self.magnitude_squared();
+        if denom <= sys::Leap_EPSILON {
+            return Vector::ZERO;
+        }
+        self / denom.sqrt()
+    }
+
+    /// Returns whether all components of this vector are finite.
+    pub fn is_finite(self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    /// Returns the components of this vector as an array.
+    pub fn to_array(self) -> [f32; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl fmt::Debug for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl From<[f32; 3]> for Vector {
+    fn from([x, y, z]: [f32; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<Vector> for [f32; 3] {
+    fn from(v: Vector) -> Self {
+        v.to_array()
+    }
+}
+
+impl Index<usize> for Vector {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("vector index out of bounds: {}", index),
+        }
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Vector {
+        Vector::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Vector {
+        Vector::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<f32> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: f32) -> Vector {
+        Vector::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Mul<Vector> for f32 {
+    type Output = Vector;
+
+    fn mul(self, rhs: Vector) -> Vector {
+        rhs * self
+    }
+}
+
+impl Div<f32> for Vector {
+    type Output = Vector;
+
+    fn div(self, rhs: f32) -> Vector {
+        Vector::new(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Vector {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign<f32> for Vector {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign<f32> for Vector {
+    fn div_assign(&mut self, rhs: f32) {
+        *self = *self / rhs;
+    }
+}
+
+/// An affine transformation consisting of 3 basis vectors and a translation.
+///
+/// Mirrors `Leap::Matrix`. Orientations reported by the SDK (eg. [`Hand::basis`]) are returned
+/// as a [`Matrix`] whose origin is zero.
+///
+/// [`Hand::basis`]: crate::hand::Hand::basis
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Matrix {
+    pub x_basis: Vector,
+    pub y_basis: Vector,
+    pub z_basis: Vector,
+    pub origin: Vector,
+}
+
+impl Matrix {
+    /// The identity transformation.
+    pub const IDENTITY: Self =
+        Self::new(Vector::X_AXIS, Vector::Y_AXIS, Vector::Z_AXIS, Vector::ZERO);
+
+    /// Creates a matrix from its basis vectors and origin.
+    #[inline]
+    pub const fn new(x_basis: Vector, y_basis: Vector, z_basis: Vector, origin: Vector) -> Self {
+        Self {
+            x_basis,
+            y_basis,
+            z_basis,
+            origin,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn from_raw(raw: sys::Leap_Matrix) -> Self {
+        Self::new(
+            Vector::from_raw(raw.xBasis),
+            Vector::from_raw(raw.yBasis),
+            Vector::from_raw(raw.zBasis),
+            Vector::from_raw(raw.origin),
+        )
+    }
+
+    /// Creates a matrix describing a rotation of `angle` radians around `axis`.
+    ///
+    /// Like `Leap::Matrix::setRotation`, this stores the rows of the usual right-handed rotation
+    /// matrix in the basis vectors, so [`Matrix::transform_direction`] rotates by `-angle`.
+    pub fn from_axis_angle(axis: Vector, angle: f32) -> Self {
+        let [x, y, z] = axis.normalized().to_array();
+        let (s, c) = angle.sin_cos();
+        let t = 1.0 - c;
+
+        Self::new(
+            Vector::new(x * x * t + c, x * y * t - z * s, x * z * t + y * s),
+            Vector::new(y * x * t + z * s, y * y * t + c, y * z * t - x * s),
+            Vector::new(z * x * t - y * s, z * y * t + x * s, z * z * t + c),
+            Vector::ZERO,
+        )
+    }
+
+    /// Transforms a point, applying rotation, scale, and translation.
+    pub fn transform_point(&self, point: Vector) -> Vector {
+        self.transform_direction(point) + self.origin
+    }
+
+    /// Transforms a direction, applying rotation and scale, but not translation.
+    pub fn transform_direction(&self, direction: Vector) -> Vector {
+        self.x_basis * direction.x + self.y_basis * direction.y + self.z_basis * direction.z
+    }
+
+    /// Returns the inverse of this matrix, assuming that it is a rigid transformation (a rotation
+    /// followed by a translation, without any scaling).
+    ///
+    /// This is cheaper than inverting a general affine transformation.
+    pub fn rigid_inverse(&self) -> Matrix {
+        let (x, y, z) = (self.x_basis, self.y_basis, self.z_basis);
+        let mut inverse = Matrix::new(
+            Vector::new(x.x, y.x, z.x),
+            Vector::new(x.y, y.y, z.y),
+            Vector::new(x.z, y.z, z.z),
+            Vector::ZERO,
+        );
+        inverse.origin = inverse.transform_direction(-self.origin);
+        inverse
+    }
+
+    /// Returns the basis vectors as the rows of a 3x3 array.
+    pub fn to_array_3x3(&self) -> [[f32; 3]; 3] {
+        [
+            self.x_basis.to_array(),
+            self.y_basis.to_array(),
+            self.z_basis.to_array(),
+        ]
+    }
+
+    /// Returns the basis vectors and origin as the rows of a 4x4 array.
+    ///
+    /// This is the layout used by `Leap::Matrix::toArray4x4`, and can be interpreted as a
+    /// column-major 4x4 matrix acting on column vectors.
+    pub fn to_array_4x4(&self) -> [[f32; 4]; 4] {
+        let row = |v: Vector, w| [v.x, v.y, v.z, w];
+        [
+            row(self.x_basis, 0.0),
+            row(self.y_basis, 0.0),
+            row(self.z_basis, 0.0),
+            row(self.origin, 1.0),
+        ]
+    }
+}
+
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul for Matrix {
+    type Output = Matrix;
+
+    /// Composes two transformations, so that `rhs` is applied first.
+    fn mul(self, rhs: Matrix) -> Matrix {
+        Matrix::new(
+            self.transform_direction(rhs.x_basis),
+            self.transform_direction(rhs.y_basis),
+            self.transform_direction(rhs.z_basis),
+            self.transform_point(rhs.origin),
+        )
+    }
+}
+
+impl MulAssign for Matrix {
+    fn mul_assign(&mut self, rhs: Matrix) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    use super::*;
+
+    fn assert_close(a: Vector, b: Vector) {
+        assert!(a.distance_to(b) < 1e-5, "{:?} != {:?}", a, b);
+    }
+
+    fn assert_matrix_close(a: Matrix, b: Matrix) {
+        assert_close(a.x_basis, b.x_basis);
+        assert_close(a.y_basis, b.y_basis);
+        assert_close(a.z_basis, b.z_basis);
+        assert_close(a.origin, b.origin);
+    }
+
+    #[test]
+    fn cross() {
+        assert_eq!(Vector::X_AXIS.cross(Vector::Y_AXIS), Vector::Z_AXIS);
+        assert_eq!(Vector::Y_AXIS.cross(Vector::Z_AXIS), Vector::X_AXIS);
+        assert_eq!(Vector::Z_AXIS.cross(Vector::X_AXIS), Vector::Y_AXIS);
+        assert_eq!(Vector::Y_AXIS.cross(Vector::X_AXIS), -Vector::Z_AXIS);
+
+        let a = Vector::new(1.0, 2.0, 3.0);
+        let b = Vector::new(-4.0, 5.0, 0.5);
+        assert_eq!(a.cross(b), Vector::new(-14.0, -12.5, 13.0));
+        assert_eq!(a.cross(a), Vector::ZERO);
+    }
+
+    #[test]
+    fn angle_to() {
+        assert_eq!(Vector::X_AXIS.angle_to(Vector::X_AXIS * 3.0), 0.0);
+        assert_eq!(Vector::X_AXIS.angle_to(Vector::Y_AXIS), FRAC_PI_2);
+        assert_eq!(Vector::X_AXIS.angle_to(-Vector::X_AXIS), PI);
+        assert!((Vector::X_AXIS.angle_to(Vector::new(1.0, 1.0, 0.0)) - FRAC_PI_4).abs() < 1e-6);
+        assert_eq!(Vector::ZERO.angle_to(Vector::X_AXIS), 0.0);
+    }
+
+    #[test]
+    fn pitch_yaw_roll() {
+        assert_eq!(Vector::FORWARD.pitch(), 0.0);
+        assert_eq!(Vector::FORWARD.yaw(), 0.0);
+        assert_eq!(Vector::UP.pitch(), FRAC_PI_2);
+        assert_eq!(Vector::DOWN.pitch(), -FRAC_PI_2);
+        assert_eq!(Vector::RIGHT.yaw(), FRAC_PI_2);
+        assert_eq!(Vector::LEFT.yaw(), -FRAC_PI_2);
+        assert_eq!(Vector::new(1.0, 0.0, -1.0).yaw(), FRAC_PI_4);
+
+        // A palm facing down has no roll.
+        assert_eq!(Vector::DOWN.roll(), 0.0);
+        assert_eq!(Vector::RIGHT.roll(), FRAC_PI_2);
+        assert_eq!(Vector::LEFT.roll(), -FRAC_PI_2);
+    }
+
+    #[test]
+    fn from_axis_angle() {
+        let rotation = Matrix::from_axis_angle(Vector::Z_AXIS * 2.0, FRAC_PI_2);
+        // The SDK convention is the transpose of the usual rotation matrix.
+        assert_matrix_close(
+            rotation,
+            Matrix::new(
+                Vector::new(0.0, -1.0, 0.0),
+                Vector::new(1.0, 0.0, 0.0),
+                Vector::Z_AXIS,
+                Vector::ZERO,
+            ),
+        );
+        assert_close(rotation.transform_direction(Vector::X_AXIS), Vector::DOWN);
+        assert_close(rotation.transform_direction(Vector::Z_AXIS), Vector::Z_AXIS);
+
+        assert_matrix_close(
+            Matrix::from_axis_angle(Vector::Y_AXIS, 0.0),
+            Matrix::IDENTITY,
+        );
+    }
+
+    #[test]
+    fn multiply() {
+        let rotation = Matrix::from_axis_angle(Vector::Z_AXIS, FRAC_PI_2);
+        let mut translation = Matrix::IDENTITY;
+        translation.origin = Vector::new(10.0, 0.0, 0.0);
+
+        // `rhs` is applied first.
+        let point = Vector::new(1.0, 2.0, 3.0);
+        assert_close(
+            (rotation * translation).transform_point(point),
+            rotation.transform_point(translation.transform_point(point)),
+        );
+        assert_close(
+            (rotation * translation).transform_point(Vector::ZERO),
+            Vector::new(0.0, -10.0, 0.0),
+        );
+        assert_close(
+            (translation * rotation).transform_point(Vector::ZERO),
+            Vector::new(10.0, 0.0, 0.0),
+        );
+
+        let mut m = rotation;
+        m *= rotation;
+        assert_matrix_close(m, Matrix::from_axis_angle(Vector::Z_AXIS, PI));
+        assert_eq!(rotation * Matrix::IDENTITY, rotation);
+    }
+
+    #[test]
+    fn rigid_inverse() {
+        let mut m = Matrix::from_axis_angle(Vector::new(1.0, 2.0, -0.5), 1.2);
+        m.origin = Vector::new(5.0, -3.0, 20.0);
+        let inverse = m.rigid_inverse();
+
+        assert_matrix_close(m * inverse, Matrix::IDENTITY);
+        assert_matrix_close(inverse * m, Matrix::IDENTITY);
+        let point = Vector::new(7.0, 8.0, 9.0);
+        assert_close(inverse.transform_point(m.transform_point(point)), point);
+    }
+}
@@ -0,0 +1,73 @@
+use ::cgmath::{Matrix4, Point3, Vector3};
+
+use crate::{Matrix, Vector};
+
+impl From<Vector> for Vector3<f32> {
+    fn from(v: Vector) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector3<f32>> for Vector {
+    fn from(v: Vector3<f32>) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector> for Point3<f32> {
+    fn from(v: Vector) -> Self {
+        Point3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Point3<f32>> for Vector {
+    fn from(v: Point3<f32>) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Matrix> for Matrix4<f32> {
+    fn from(m: Matrix) -> Self {
+        m.to_array_4x4().into()
+    }
+}
+
+/// Drops the bottom row of the matrix, which is assumed to be `[0, 0, 0, 1]`.
+impl From<Matrix4<f32>> for Matrix {
+    fn from(m: Matrix4<f32>) -> Self {
+        Self::new(
+            m.x.truncate().into(),
+            m.y.truncate().into(),
+            m.z.truncate().into(),
+            m.w.truncate().into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A matrix that is not symmetric, to catch row/column mixups.
+    const M: Matrix = Matrix::new(
+        Vector::new(1.0, 2.0, 3.0),
+        Vector::new(4.0, 5.0, 6.0),
+        Vector::new(7.0, 8.0, 9.0),
+        Vector::new(10.0, 11.0, 12.0),
+    );
+    const V: Vector = Vector::new(1.0, -2.0, 3.5);
+
+    #[test]
+    fn vector_round_trip() {
+        assert_eq!(Vector::from(Vector3::from(V)), V);
+        assert_eq!(Vector::from(Point3::from(V)), V);
+    }
+
+    #[test]
+    fn matrix_round_trip() {
+        let m = Matrix4::from(M);
+        assert_eq!(m.x, Vector3::new(1.0, 2.0, 3.0).extend(0.0));
+        assert_eq!(m.w, Vector3::new(10.0, 11.0, 12.0).extend(1.0));
+        assert_eq!(Matrix::from(m), M);
+    }
+}
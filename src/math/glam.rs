@@ -0,0 +1,104 @@
+use ::glam::{Affine3A, Mat3, Mat4, Vec3, Vec3A};
+
+use crate::{Matrix, Vector};
+
+impl From<Vector> for Vec3 {
+    fn from(v: Vector) -> Self {
+        Vec3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vec3> for Vector {
+    fn from(v: Vec3) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector> for Vec3A {
+    fn from(v: Vector) -> Self {
+        Vec3A::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vec3A> for Vector {
+    fn from(v: Vec3A) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Matrix> for Mat4 {
+    fn from(m: Matrix) -> Self {
+        Mat4::from_cols_array_2d(&m.to_array_4x4())
+    }
+}
+
+/// Drops the bottom row of the matrix, which is assumed to be `[0, 0, 0, 1]`.
+impl From<Mat4> for Matrix {
+    fn from(m: Mat4) -> Self {
+        Self::new(
+            m.x_axis.truncate().into(),
+            m.y_axis.truncate().into(),
+            m.z_axis.truncate().into(),
+            m.w_axis.truncate().into(),
+        )
+    }
+}
+
+impl From<Matrix> for Affine3A {
+    fn from(m: Matrix) -> Self {
+        Affine3A::from_mat3_translation(
+            Mat3::from_cols(m.x_basis.into(), m.y_basis.into(), m.z_basis.into()),
+            m.origin.into(),
+        )
+    }
+}
+
+impl From<Affine3A> for Matrix {
+    fn from(m: Affine3A) -> Self {
+        Self::new(
+            m.matrix3.x_axis.into(),
+            m.matrix3.y_axis.into(),
+            m.matrix3.z_axis.into(),
+            m.translation.into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A matrix that is not symmetric, to catch row/column mixups.
+    const M: Matrix = Matrix::new(
+        Vector::new(1.0, 2.0, 3.0),
+        Vector::new(4.0, 5.0, 6.0),
+        Vector::new(7.0, 8.0, 9.0),
+        Vector::new(10.0, 11.0, 12.0),
+    );
+    const V: Vector = Vector::new(1.0, -2.0, 3.5);
+
+    #[test]
+    fn vector_round_trip() {
+        assert_eq!(Vector::from(Vec3::from(V)), V);
+        assert_eq!(Vector::from(Vec3A::from(V)), V);
+    }
+
+    #[test]
+    fn matrix_round_trip() {
+        let m = Mat4::from(M);
+        assert_eq!(m.x_axis, Vec3::new(1.0, 2.0, 3.0).extend(0.0));
+        assert_eq!(m.w_axis, Vec3::new(10.0, 11.0, 12.0).extend(1.0));
+        assert_eq!(
+            Vec3::from(M.transform_point(V)),
+            m.transform_point3(V.into())
+        );
+        assert_eq!(Matrix::from(m), M);
+
+        let affine = Affine3A::from(M);
+        assert_eq!(
+            Vec3::from(M.transform_point(V)),
+            affine.transform_point3(V.into())
+        );
+        assert_eq!(Matrix::from(affine), M);
+    }
+}
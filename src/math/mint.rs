@@ -0,0 +1,75 @@
+use crate::{Matrix, Vector};
+
+impl From<Vector> for ::mint::Vector3<f32> {
+    fn from(v: Vector) -> Self {
+        Self {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl From<::mint::Vector3<f32>> for Vector {
+    fn from(v: ::mint::Vector3<f32>) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector> for ::mint::Point3<f32> {
+    fn from(v: Vector) -> Self {
+        Self {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl From<::mint::Point3<f32>> for Vector {
+    fn from(v: ::mint::Point3<f32>) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Matrix> for ::mint::ColumnMatrix4<f32> {
+    fn from(m: Matrix) -> Self {
+        m.to_array_4x4().into()
+    }
+}
+
+/// Drops the bottom row of the matrix, which is assumed to be `[0, 0, 0, 1]`.
+impl From<::mint::ColumnMatrix4<f32>> for Matrix {
+    fn from(m: ::mint::ColumnMatrix4<f32>) -> Self {
+        let col = |c: ::mint::Vector4<f32>| Vector::new(c.x, c.y, c.z);
+        Self::new(col(m.x), col(m.y), col(m.z), col(m.w))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A matrix that is not symmetric, to catch row/column mixups.
+    const M: Matrix = Matrix::new(
+        Vector::new(1.0, 2.0, 3.0),
+        Vector::new(4.0, 5.0, 6.0),
+        Vector::new(7.0, 8.0, 9.0),
+        Vector::new(10.0, 11.0, 12.0),
+    );
+    const V: Vector = Vector::new(1.0, -2.0, 3.5);
+
+    #[test]
+    fn vector_round_trip() {
+        assert_eq!(Vector::from(::mint::Vector3::from(V)), V);
+        assert_eq!(Vector::from(::mint::Point3::from(V)), V);
+    }
+
+    #[test]
+    fn matrix_round_trip() {
+        let m = ::mint::ColumnMatrix4::from(M);
+        assert_eq!(m.x, [1.0, 2.0, 3.0, 0.0].into());
+        assert_eq!(m.w, [10.0, 11.0, 12.0, 1.0].into());
+        assert_eq!(Matrix::from(m), M);
+    }
+}
@@ -0,0 +1,79 @@
+use ::nalgebra::{Matrix4, Point3, Vector3, Vector4};
+
+use crate::{Matrix, Vector};
+
+impl From<Vector> for Vector3<f32> {
+    fn from(v: Vector) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector3<f32>> for Vector {
+    fn from(v: Vector3<f32>) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector> for Point3<f32> {
+    fn from(v: Vector) -> Self {
+        Point3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Point3<f32>> for Vector {
+    fn from(v: Point3<f32>) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Matrix> for Matrix4<f32> {
+    fn from(m: Matrix) -> Self {
+        let col = |v: Vector, w| Vector4::new(v.x, v.y, v.z, w);
+        Matrix4::from_columns(&[
+            col(m.x_basis, 0.0),
+            col(m.y_basis, 0.0),
+            col(m.z_basis, 0.0),
+            col(m.origin, 1.0),
+        ])
+    }
+}
+
+/// Drops the bottom row of the matrix, which is assumed to be `[0, 0, 0, 1]`.
+impl From<Matrix4<f32>> for Matrix {
+    fn from(m: Matrix4<f32>) -> Self {
+        let col = |i| Vector::new(m[(0, i)], m[(1, i)], m[(2, i)]);
+        Self::new(col(0), col(1), col(2), col(3))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A matrix that is not symmetric, to catch row/column mixups.
+    const M: Matrix = Matrix::new(
+        Vector::new(1.0, 2.0, 3.0),
+        Vector::new(4.0, 5.0, 6.0),
+        Vector::new(7.0, 8.0, 9.0),
+        Vector::new(10.0, 11.0, 12.0),
+    );
+    const V: Vector = Vector::new(1.0, -2.0, 3.5);
+
+    #[test]
+    fn vector_round_trip() {
+        assert_eq!(Vector::from(Vector3::from(V)), V);
+        assert_eq!(Vector::from(Point3::from(V)), V);
+    }
+
+    #[test]
+    fn matrix_round_trip() {
+        let m = Matrix4::from(M);
+        assert_eq!(m.column(0), Vector4::new(1.0, 2.0, 3.0, 0.0));
+        assert_eq!(m.column(3), Vector4::new(10.0, 11.0, 12.0, 1.0));
+        assert_eq!(
+            Point3::from(M.transform_point(V)),
+            m.transform_point(&V.into()),
+        );
+        assert_eq!(Matrix::from(m), M);
+    }
+}
@@ -0,0 +1,652 @@
+//! Mapping of hand features to MIDI messages.
+//!
+//! A [`Mapper`] turns features of the tracked hands (see [`HandFeature`]) into MIDI control
+//! changes and notes according to a list of [`Mapping`]s, and sends them to a [`MidiPort`]. It
+//! operates on [`HandSnapshot`]s, and can be added to a controller as a [`Listener`].
+//!
+//! This module does not talk to MIDI hardware or sequencers itself: [`WritePort`] writes raw MIDI
+//! bytes to any [`Write`] implementation (eg. an ALSA raw MIDI device like `/dev/snd/midiC1D0`),
+//! and [`VirtualPort`] collects the messages in memory.
+
+use std::{
+    f32::consts::FRAC_PI_2,
+    io::{self, Write},
+    mem,
+    sync::{Arc, Mutex},
+};
+
+use crate::{recognize::HandSnapshot, ControllerRef, Listener};
+
+/// A MIDI channel message.
+///
+/// Channels are numbered from 0 to 15; all other numbers range from 0 to 127.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MidiMessage {
+    NoteOn {
+        channel: u8,
+        note: u8,
+        velocity: u8,
+    },
+    NoteOff {
+        channel: u8,
+        note: u8,
+    },
+    ControlChange {
+        channel: u8,
+        controller: u8,
+        value: u8,
+    },
+}
+
+impl MidiMessage {
+    /// Returns the wire representation of the message.
+    pub fn to_bytes(self) -> [u8; 3] {
+        match self {
+            MidiMessage::NoteOn {
+                channel,
+                note,
+                velocity,
+            } => [0x90 | channel & 0xf, note & 0x7f, velocity & 0x7f],
+            MidiMessage::NoteOff { channel, note } => [0x80 | channel & 0xf, note & 0x7f, 0],
+            MidiMessage::ControlChange {
+                channel,
+                controller,
+                value,
+            } => [0xb0 | channel & 0xf, controller & 0x7f, value & 0x7f],
+        }
+    }
+}
+
+/// A destination for MIDI messages.
+pub trait MidiPort: Send + 'static {
+    fn send(&mut self, message: MidiMessage) -> io::Result<()>;
+}
+
+/// A [`MidiPort`] writing the raw MIDI bytes of each message to a [`Write`] implementation.
+#[derive(Debug)]
+pub struct WritePort<W>(pub W);
+
+impl<W: Write + Send + 'static> MidiPort for WritePort<W> {
+    fn send(&mut self, message: MidiMessage) -> io::Result<()> {
+        self.0.write_all(&message.to_bytes())?;
+        self.0.flush()
+    }
+}
+
+/// A [`MidiPort`] that stores the messages sent to it.
+///
+/// Clones of a [`VirtualPort`] share the same messages, so one clone can be handed to a [`Mapper`]
+/// while another is used to inspect the output.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualPort {
+    messages: Arc<Mutex<Vec<MidiMessage>>>,
+}
+
+impl VirtualPort {
+    /// Creates a port without messages.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes and returns all messages sent so far.
+    pub fn take(&self) -> Vec<MidiMessage> {
+        mem::take(&mut *self.messages.lock().unwrap())
+    }
+}
+
+impl MidiPort for VirtualPort {
+    fn send(&mut self, message: MidiMessage) -> io::Result<()> {
+        self.messages.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+/// A feature of a hand that can be mapped to MIDI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HandFeature {
+    /// The height of the palm above the device, in millimeters.
+    PalmHeight,
+    /// The roll of the palm, in radians; positive when the palm turns to the left.
+    Roll,
+    /// [`HandSnapshot::pinch_strength`].
+    Pinch,
+    /// [`HandSnapshot::grab_strength`].
+    Grab,
+    /// The mean angle between the directions of adjacent fingers, in radians.
+    FingerSpread,
+}
+
+impl HandFeature {
+    /// Returns the value of this feature for `hand`.
+    pub fn value(self, hand: &HandSnapshot) -> f32 {
+        match self {
+            HandFeature::PalmHeight => hand.palm_position.y,
+            HandFeature::Roll => hand.palm_normal.roll(),
+            HandFeature::Pinch => hand.pinch_strength,
+            HandFeature::Grab => hand.grab_strength,
+            HandFeature::FingerSpread => {
+                let angles = hand
+                    .fingers
+                    .windows(2)
+                    .map(|pair| pair[0].direction.angle_to(pair[1].direction));
+                angles.sum::<f32>() / (hand.fingers.len() - 1) as f32
+            }
+        }
+    }
+
+    /// Returns the typical range of values of this feature, used by [`Mapping::new`].
+    pub fn default_range(self) -> (f32, f32) {
+        match self {
+            HandFeature::PalmHeight => (100.0, 400.0),
+            HandFeature::Roll => (-FRAC_PI_2, FRAC_PI_2),
+            HandFeature::Pinch | HandFeature::Grab => (0.0, 1.0),
+            HandFeature::FingerSpread => (0.0, 0.4),
+        }
+    }
+}
+
+/// The shape of the transfer function from normalized feature values to MIDI values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Curve {
+    Linear,
+    /// Raises the normalized value to the given power: exponents above 1 give more resolution to
+    /// low values, exponents below 1 to high values.
+    Power(f32),
+    /// A smoothstep curve, giving more resolution to the middle of the range.
+    SCurve,
+}
+
+impl Curve {
+    /// Applies the curve to a value between 0 and 1.
+    pub fn apply(self, x: f32) -> f32 {
+        match self {
+            Curve::Linear => x,
+            Curve::Power(exponent) => x.powf(exponent),
+            Curve::SCurve => x * x * (3.0 - 2.0 * x),
+        }
+    }
+}
+
+/// Which hand a [`Mapping`] follows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HandSelector {
+    Left,
+    Right,
+    /// The first hand reported in a frame.
+    Any,
+}
+
+impl HandSelector {
+    fn matches(self, hand: &HandSnapshot) -> bool {
+        match self {
+            HandSelector::Left => hand.is_left,
+            HandSelector::Right => !hand.is_left,
+            HandSelector::Any => true,
+        }
+    }
+}
+
+/// The MIDI messages a [`Mapping`] produces.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Target {
+    /// Sets a controller to the mapped value, from 0 to 127.
+    ControlChange { channel: u8, controller: u8 },
+    /// Plays the note corresponding to the mapped value, from `low` to `high`.
+    ///
+    /// When the value moves to another note, the previous note is stopped.
+    Note {
+        channel: u8,
+        low: u8,
+        high: u8,
+        velocity: u8,
+    },
+    /// Plays `note` while the mapped value is at least `threshold` (between 0 and 1).
+    Gate {
+        channel: u8,
+        note: u8,
+        velocity: u8,
+        threshold: f32,
+    },
+}
+
+/// Maps one [`HandFeature`] to MIDI.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mapping {
+    pub feature: HandFeature,
+    pub hand: HandSelector,
+    /// The feature value mapped to the lowest MIDI value. Values outside of `min..=max` are
+    /// clamped; `min` may be larger than `max` to invert the mapping.
+    pub min: f32,
+    /// The feature value mapped to the highest MIDI value.
+    pub max: f32,
+    pub curve: Curve,
+    /// How much of the previous value is kept in every frame, from 0 (no smoothing) to just below
+    /// 1. Applied to the normalized value before the curve.
+    pub smoothing: f32,
+    pub target: Target,
+}
+
+impl Mapping {
+    /// Creates a linear mapping of `feature` over its [`HandFeature::default_range`], following any
+    /// hand, with light smoothing.
+    pub fn new(feature: HandFeature, target: Target) -> Self {
+        let (min, max) = feature.default_range();
+        Self {
+            feature,
+            hand: HandSelector::Any,
+            min,
+            max,
+            curve: Curve::Linear,
+            smoothing: 0.5,
+            target,
+        }
+    }
+
+    /// Returns `value` normalized to the range of this mapping, between 0 and 1.
+    fn normalize(&self, value: f32) -> f32 {
+        let t = (value - self.min) / (self.max - self.min);
+        if t.is_nan() {
+            0.0
+        } else {
+            t.clamp(0.0, 1.0)
+        }
+    }
+}
+
+/// The state of a [`Mapping`] between frames.
+#[derive(Debug, Default)]
+struct MappingState {
+    /// The smoothed, normalized value, if the hand was present in the last frame.
+    smoothed: Option<f32>,
+    /// The last controller value sent, or the note currently playing.
+    sent: Option<u8>,
+}
+
+/// Sends MIDI messages derived from the tracked hands to a [`MidiPort`].
+///
+/// Control changes are only sent when the controller value changes. When the hand of a mapping is
+/// lost, its note is stopped and its controller keeps the last value.
+pub struct Mapper<P> {
+    port: P,
+    mappings: Vec<(Mapping, MappingState)>,
+}
+
+impl<P: MidiPort> Mapper<P> {
+    /// Creates a mapper without mappings.
+    pub fn new(port: P) -> Self {
+        Self {
+            port,
+            mappings: Vec::new(),
+        }
+    }
+
+    /// Adds a mapping.
+    pub fn add_mapping(&mut self, mapping: Mapping) {
+        self.mappings.push((mapping, MappingState::default()));
+    }
+
+    /// Returns the port the messages are sent to.
+    pub fn port(&self) -> &P {
+        &self.port
+    }
+
+    /// Processes the hands of the next frame, sending the resulting messages.
+    pub fn update(&mut self, hands: &[HandSnapshot]) -> io::Result<()> {
+        for (mapping, state) in &mut self.mappings {
+            let hand = hands.iter().find(|hand| mapping.hand.matches(hand));
+            let value = hand.map(|hand| mapping.normalize(mapping.feature.value(hand)));
+
+            let value = match (value, state.smoothed) {
+                (Some(value), Some(previous)) => {
+                    Some(previous * mapping.smoothing + value * (1.0 - mapping.smoothing))
+                }
+                (value, _) => value,
+            };
+            state.smoothed = value;
+
+            let value = value.map(|value| mapping.curve.apply(value));
+            apply(&mut self.port, mapping.target, state, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Stops all playing notes and forgets the state of the mappings.
+    pub fn release(&mut self) -> io::Result<()> {
+        for (mapping, state) in &mut self.mappings {
+            apply(&mut self.port, mapping.target, state, None)?;
+            *state = MappingState::default();
+        }
+
+        Ok(())
+    }
+}
+
+/// Sends the messages for the new `value` of a mapping, or `None` if its hand was lost.
+fn apply(
+    port: &mut impl MidiPort,
+    target: Target,
+    state: &mut MappingState,
+    value: Option<f32>,
+) -> io::Result<()> {
+    match target {
+        Target::ControlChange {
+            channel,
+            controller,
+        } => {
+            let value = match value {
+                Some(value) => (value * 127.0).round() as u8,
+                None => return Ok(()),
+            };
+            if state.sent != Some(value) {
+                port.send(MidiMessage::ControlChange {
+                    channel,
+                    controller,
+                    value,
+                })?;
+                state.sent = Some(value);
+            }
+        }
+        Target::Note {
+            channel,
+            low,
+            high,
+            velocity,
+        } => {
+            let note = value.map(|value| {
+                let range = f32::from(high) - f32::from(low);
+                (f32::from(low) + value * range).round() as u8
+            });
+            play(port, channel, velocity, state, note)?;
+        }
+        Target::Gate {
+            channel,
+            note,
+            velocity,
+            threshold,
+        } => {
+            let note = value.filter(|value| *value >= threshold).map(|_| note);
+            play(port, channel, velocity, state, note)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Makes `note` the playing note of a mapping, stopping the previous one.
+fn play(
+    port: &mut impl MidiPort,
+    channel: u8,
+    velocity: u8,
+    state: &mut MappingState,
+    note: Option<u8>,
+) -> io::Result<()> {
+    if state.sent == note {
+        return Ok(());
+    }
+
+    if let Some(note) = state.sent.take() {
+        port.send(MidiMessage::NoteOff { channel, note })?;
+    }
+    if let Some(note) = note {
+        port.send(MidiMessage::NoteOn {
+            channel,
+            note,
+            velocity,
+        })?;
+        state.sent = Some(note);
+    }
+
+    Ok(())
+}
+
+impl<P: MidiPort> Listener for Mapper<P> {
+    fn on_frame(&mut self, controller: &ControllerRef) {
+        let hands = match controller.frame_data() {
+            Some(frame) => HandSnapshot::from_frame_data(&frame),
+            None => return,
+        };
+        if let Err(e) = self.update(&hands) {
+            log::error!("failed to send MIDI message: {}", e);
+        }
+    }
+
+    fn on_disconnect(&mut self, _: &ControllerRef) {
+        if let Err(e) = self.release() {
+            log::error!("failed to send MIDI message: {}", e);
+        }
+    }
+
+    fn on_exit(&mut self, _: &ControllerRef) {
+        if let Err(e) = self.release() {
+            log::error!("failed to send MIDI message: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{recognize::FingerSnapshot, Timestamp, Vector};
+
+    fn hand(is_left: bool) -> HandSnapshot {
+        HandSnapshot {
+            timestamp: Timestamp::from_raw(0),
+            id: 1,
+            is_left,
+            palm_position: Vector::new(0.0, 200.0, 0.0),
+            palm_velocity: Vector::ZERO,
+            palm_normal: Vector::DOWN,
+            direction: Vector::FORWARD,
+            pinch_strength: 0.0,
+            grab_strength: 0.0,
+            fingers: [FingerSnapshot::default(); 5],
+        }
+    }
+
+    fn at_height(y: f32) -> HandSnapshot {
+        let mut hand = hand(false);
+        hand.palm_position.y = y;
+        hand
+    }
+
+    fn grabbing(strength: f32) -> HandSnapshot {
+        HandSnapshot {
+            grab_strength: strength,
+            ..hand(false)
+        }
+    }
+
+    fn mapper(mapping: Mapping) -> Mapper<VirtualPort> {
+        let mut mapper = Mapper::new(VirtualPort::new());
+        mapper.add_mapping(Mapping {
+            smoothing: 0.0,
+            ..mapping
+        });
+        mapper
+    }
+
+    const CC: Target = Target::ControlChange {
+        channel: 1,
+        controller: 7,
+    };
+
+    fn cc(value: u8) -> MidiMessage {
+        MidiMessage::ControlChange {
+            channel: 1,
+            controller: 7,
+            value,
+        }
+    }
+
+    fn note_on(note: u8) -> MidiMessage {
+        MidiMessage::NoteOn {
+            channel: 2,
+            note,
+            velocity: 100,
+        }
+    }
+
+    fn note_off(note: u8) -> MidiMessage {
+        MidiMessage::NoteOff { channel: 2, note }
+    }
+
+    #[test]
+    fn control_change_only_when_value_changes() {
+        let mut mapper = mapper(Mapping {
+            min: 0.0,
+            max: 127.0,
+            ..Mapping::new(HandFeature::PalmHeight, CC)
+        });
+
+        mapper.update(&[at_height(64.0)]).unwrap();
+        assert_eq!(mapper.port().take(), [cc(64)]);
+        mapper.update(&[at_height(64.2)]).unwrap();
+        assert_eq!(mapper.port().take(), []);
+        mapper.update(&[at_height(65.0)]).unwrap();
+        assert_eq!(mapper.port().take(), [cc(65)]);
+        // Losing the hand keeps the last value.
+        mapper.update(&[]).unwrap();
+        assert_eq!(mapper.port().take(), []);
+    }
+
+    #[test]
+    fn smoothing_converges() {
+        let mut mapper = Mapper::new(VirtualPort::new());
+        mapper.add_mapping(Mapping {
+            min: 0.0,
+            max: 127.0,
+            smoothing: 0.5,
+            ..Mapping::new(HandFeature::PalmHeight, CC)
+        });
+
+        mapper.update(&[at_height(0.0)]).unwrap();
+        assert_eq!(mapper.port().take(), [cc(0)]);
+        for _ in 0..20 {
+            mapper.update(&[at_height(127.0)]).unwrap();
+        }
+        let values: Vec<u8> = mapper
+            .port()
+            .take()
+            .into_iter()
+            .map(|message| match message {
+                MidiMessage::ControlChange { value, .. } => value,
+                _ => panic!("unexpected message {:?}", message),
+            })
+            .collect();
+        assert_eq!(values[0], 64);
+        assert!(
+            values.windows(2).all(|pair| pair[0] < pair[1]),
+            "{:?}",
+            values
+        );
+        assert_eq!(values.last(), Some(&127));
+
+        mapper.update(&[at_height(127.0)]).unwrap();
+        assert_eq!(mapper.port().take(), []);
+    }
+
+    #[test]
+    fn note_moves() {
+        let mut mapper = mapper(Mapping::new(
+            HandFeature::Grab,
+            Target::Note {
+                channel: 2,
+                low: 60,
+                high: 72,
+                velocity: 100,
+            },
+        ));
+
+        mapper.update(&[grabbing(0.0)]).unwrap();
+        assert_eq!(mapper.port().take(), [note_on(60)]);
+        mapper.update(&[grabbing(0.5)]).unwrap();
+        assert_eq!(mapper.port().take(), [note_off(60), note_on(66)]);
+        mapper.update(&[grabbing(0.51)]).unwrap();
+        assert_eq!(mapper.port().take(), []);
+        mapper.update(&[]).unwrap();
+        assert_eq!(mapper.port().take(), [note_off(66)]);
+    }
+
+    #[test]
+    fn gate_threshold() {
+        let mut mapper = mapper(Mapping::new(
+            HandFeature::Grab,
+            Target::Gate {
+                channel: 2,
+                note: 36,
+                velocity: 100,
+                threshold: 0.5,
+            },
+        ));
+
+        mapper.update(&[grabbing(0.4)]).unwrap();
+        assert_eq!(mapper.port().take(), []);
+        mapper.update(&[grabbing(0.5)]).unwrap();
+        assert_eq!(mapper.port().take(), [note_on(36)]);
+        mapper.update(&[grabbing(0.9)]).unwrap();
+        assert_eq!(mapper.port().take(), []);
+        mapper.update(&[grabbing(0.3)]).unwrap();
+        assert_eq!(mapper.port().take(), [note_off(36)]);
+    }
+
+    #[test]
+    fn inverted_range() {
+        let mut mapper = mapper(Mapping {
+            min: 400.0,
+            max: 100.0,
+            ..Mapping::new(HandFeature::PalmHeight, CC)
+        });
+
+        for (height, value) in [
+            (400.0, 0),
+            (100.0, 127),
+            (250.0, 64),
+            (500.0, 0),
+            (0.0, 127),
+        ] {
+            mapper.update(&[at_height(height)]).unwrap();
+            assert_eq!(mapper.port().take(), [cc(value)], "height {}", height);
+        }
+    }
+
+    #[test]
+    fn hand_selector() {
+        let mut mapper = mapper(Mapping {
+            hand: HandSelector::Left,
+            ..Mapping::new(HandFeature::Grab, CC)
+        });
+
+        mapper.update(&[grabbing(1.0)]).unwrap();
+        assert_eq!(mapper.port().take(), []);
+        let left = HandSnapshot {
+            grab_strength: 1.0,
+            ..hand(true)
+        };
+        mapper.update(&[grabbing(0.0), left]).unwrap();
+        assert_eq!(mapper.port().take(), [cc(127)]);
+    }
+
+    #[test]
+    fn release_stops_note() {
+        let mut mapper = mapper(Mapping::new(
+            HandFeature::Grab,
+            Target::Gate {
+                channel: 2,
+                note: 36,
+                velocity: 100,
+                threshold: 0.5,
+            },
+        ));
+
+        mapper.update(&[grabbing(1.0)]).unwrap();
+        assert_eq!(mapper.port().take(), [note_on(36)]);
+        mapper.release().unwrap();
+        assert_eq!(mapper.port().take(), [note_off(36)]);
+        mapper.release().unwrap();
+        assert_eq!(mapper.port().take(), []);
+        // The note starts again on the next frame.
+        mapper.update(&[grabbing(1.0)]).unwrap();
+        assert_eq!(mapper.port().take(), [note_on(36)]);
+    }
+}
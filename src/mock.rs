@@ -0,0 +1,376 @@
+use std::{
+    ops::Deref,
+    panic,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+};
+
+use crate::{
+    backend::VirtualState, data::FrameData, image::ImageBuf, recording::Event, Controller,
+    ControllerBackend, ControllerRef, Error, Frame, Listener, ListenerHandle, Timestamp,
+};
+
+/// A controller whose events are scripted by the application instead of being reported by leapd.
+///
+/// This is meant for testing code that uses a [`ControllerRef`] or implements [`Listener`] without
+/// a Leap Motion device. Like with a [`Controller`], the [`Listener`]s are invoked from a
+/// background thread. The events are delivered in the order they are triggered; use
+/// [`MockController::wait_idle`] to wait until the listeners have processed them.
+///
+/// Tracking data can be injected as plain [`FrameData`] with [`MockController::push_frame_data`],
+/// or as SDK frames with [`MockController::push_frame`]. Listeners that read the frames with
+/// [`ControllerRef::frame_data`] see both.
+///
+/// Plain frame data needs nothing but this library. SDK frames, on the other hand, can only be
+/// decoded by the Leap runtime while a [`Controller`] exists: the first SDK frame makes the mock
+/// create one, which starts connecting to leapd in the background and is kept until the
+/// [`MockController`] is dropped. Use [`MockController::push_frame_data`] to stay independent of
+/// the runtime.
+///
+/// Initially, the controller is not connected to the service or a device and has no focus.
+/// [`ControllerRef::devices`] is always empty, and [`ControllerRef::config`] cannot be saved.
+///
+/// If a listener panics, the panic is propagated to the thread calling
+/// [`MockController::wait_idle`] or dropping the [`MockController`].
+pub struct MockController {
+    controller_ref: ControllerRef,
+    sender: Option<Sender<Command>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+enum Command {
+    AddListener(ListenerHandle, Box<dyn Listener>),
+    RemoveListener(ListenerHandle, Sender<Option<Box<dyn Listener>>>),
+    SetNow(Timestamp),
+    Event(Event),
+    Frame(Vec<u8>),
+    FrameData(FrameData),
+    Images(Vec<Arc<ImageBuf>>),
+    Sync(Sender<()>),
+}
+
+impl MockController {
+    /// Creates a [`MockController`] and starts its event thread.
+    pub fn new() -> Self {
+        let state = Arc::new(VirtualState::default());
+        let (sender, receiver) = mpsc::channel();
+        let thread = {
+            let state = state.clone();
+            thread::Builder::new()
+                .name("leapcpp mock controller".into())
+                .spawn(move || run(state, receiver))
+                .unwrap()
+        };
+
+        Self {
+            controller_ref: ControllerRef::from_virtual(state),
+            sender: Some(sender),
+            thread: Some(thread),
+        }
+    }
+
+    /// Adds a new [`Listener`] to the controller, which will be notified of any events.
+    ///
+    /// [`Listener::on_init`] is invoked on the event thread. Returns a handle that can be passed to
+    /// [`MockController::remove_listener`]. Unlike [`Controller::add_listener`], this never fails;
+    /// it returns a [`Result`] to match [`ControllerBackend::add_listener`].
+    pub fn add_listener<L: Listener>(&mut self, listener: L) -> Result<ListenerHandle, Error> {
+        let handle = ListenerHandle::next();
+        self.send(Command::AddListener(handle, Box::new(listener)));
+        Ok(handle)
+    }
+
+    /// Removes a [`Listener`] previously added with [`MockController::add_listener`], and returns
+    /// it.
+    ///
+    /// This blocks until the listeners have processed all previously triggered events, and
+    /// [`Listener::on_exit`] has been invoked on the removed listener.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownListener`] if `handle` does not belong to a listener of this
+    /// controller, eg. because it was already removed.
+    pub fn remove_listener(&mut self, handle: ListenerHandle) -> Result<Box<dyn Listener>, Error> {
+        let (sender, receiver) = mpsc::channel();
+        self.send(Command::RemoveListener(handle, sender));
+        match receiver.recv() {
+            Ok(listener) => listener.ok_or(Error::UnknownListener),
+            Err(_) => self.propagate_panic(),
+        }
+    }
+
+    /// Sets the timestamp returned by [`ControllerRef::now`].
+    pub fn set_now(&self, now: Timestamp) {
+        self.send(Command::SetNow(now));
+    }
+
+    /// Simulates establishing the connection to leapd.
+    pub fn connect_service(&self) {
+        self.send(Command::Event(Event::ServiceConnect));
+    }
+
+    /// Simulates losing the connection to leapd.
+    pub fn disconnect_service(&self) {
+        self.send(Command::Event(Event::ServiceDisconnect));
+    }
+
+    /// Simulates plugging in a Leap Motion Controller.
+    pub fn connect(&self) {
+        self.send(Command::Event(Event::Connect));
+    }
+
+    /// Simulates unplugging the Leap Motion Controller.
+    pub fn disconnect(&self) {
+        self.send(Command::Event(Event::Disconnect));
+    }
+
+    /// Simulates the application gaining focus.
+    pub fn gain_focus(&self) {
+        self.send(Command::Event(Event::FocusGained));
+    }
+
+    /// Simulates the application losing focus.
+    pub fn lose_focus(&self) {
+        self.send(Command::Event(Event::FocusLost));
+    }
+
+    /// Simulates a change of the device configuration.
+    pub fn change_device(&self) {
+        self.send(Command::Event(Event::DeviceChange));
+    }
+
+    /// Makes `data` the most recent frame, and notifies the listeners.
+    ///
+    /// The frame is not backed by the SDK: [`ControllerRef::frame`] returns `None` for it, while
+    /// [`ControllerRef::frame_data`] returns a copy of `data`.
+    pub fn push_frame_data(&self, data: FrameData) {
+        self.send(Command::FrameData(data));
+    }
+
+    /// Makes a copy of `frame` the most recent frame, and notifies the listeners.
+    ///
+    /// This requires the Leap runtime, see [`MockController`].
+    pub fn push_frame(&self, frame: &Frame) {
+        self.push_serialized_frame(frame.serialize());
+    }
+
+    /// Makes a frame serialized with [`Frame::serialize`] the most recent frame, and notifies the
+    /// listeners.
+    ///
+    /// If `data` does not contain a valid frame, [`ControllerRef::frame`] returns `None`.
+    ///
+    /// This requires the Leap runtime, see [`MockController`].
+    pub fn push_serialized_frame(&self, data: Vec<u8>) {
+        self.send(Command::Frame(data));
+    }
+
+    /// Replaces the images returned by [`ControllerRef::images`], and notifies the listeners.
+    pub fn push_images(&self, images: Vec<ImageBuf>) {
+        let images = images.into_iter().map(Arc::new).collect();
+        self.send(Command::Images(images));
+    }
+
+    /// Blocks the calling thread until the listeners have processed all previously triggered
+    /// events.
+    pub fn wait_idle(&self) {
+        let (sender, receiver) = mpsc::channel();
+        self.send(Command::Sync(sender));
+        if receiver.recv().is_err() {
+            self.propagate_panic();
+        }
+    }
+
+    fn send(&self, command: Command) {
+        let sender = self.sender.as_ref().unwrap();
+        if sender.send(command).is_err() {
+            self.propagate_panic();
+        }
+    }
+
+    /// Called when the event thread has exited prematurely, which means a listener panicked.
+    fn propagate_panic(&self) -> ! {
+        panic!("a listener of the `MockController` panicked");
+    }
+}
+
+impl Default for MockController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for MockController {
+    fn drop(&mut self) {
+        // Closing the channel makes the event thread call `on_exit` and exit.
+        drop(self.sender.take());
+        if let Err(payload) = self.thread.take().unwrap().join() {
+            if !thread::panicking() {
+                panic::resume_unwind(payload);
+            }
+        }
+    }
+}
+
+impl ControllerBackend for MockController {
+    fn controller_ref(&self) -> &ControllerRef {
+        &self.controller_ref
+    }
+
+    fn add_listener<L: Listener>(&mut self, listener: L) -> Result<ListenerHandle, Error> {
+        MockController::add_listener(self, listener)
+    }
+
+    fn remove_listener(&mut self, handle: ListenerHandle) -> Result<Box<dyn Listener>, Error> {
+        MockController::remove_listener(self, handle)
+    }
+}
+
+impl Deref for MockController {
+    type Target = ControllerRef;
+
+    fn deref(&self) -> &Self::Target {
+        &self.controller_ref
+    }
+}
+
+fn run(state: Arc<VirtualState>, commands: Receiver<Command>) {
+    let controller = ControllerRef::from_virtual(state.clone());
+    let mut listeners: Vec<(ListenerHandle, Box<dyn Listener>)> = Vec::new();
+    // Created for the first SDK frame, see `MockController::push_serialized_frame`.
+    let mut sdk = None;
+
+    for command in commands {
+        match command {
+            Command::AddListener(handle, mut listener) => {
+                listener.on_init(&controller);
+                listeners.push((handle, listener));
+            }
+            Command::RemoveListener(handle, removed) => {
+                let index = listeners.iter().position(|(h, _)| *h == handle);
+                let listener = index.map(|index| {
+                    let (_, mut listener) = listeners.remove(index);
+                    listener.on_exit(&controller);
+                    listener
+                });
+                removed.send(listener).ok();
+            }
+            Command::SetNow(now) => state.lock().now = now.as_raw(),
+            Command::Event(event) => {
+                event.apply(&mut state.lock());
+                for (_, listener) in &mut listeners {
+                    event.dispatch(&mut **listener, &controller);
+                }
+            }
+            Command::Frame(data) => {
+                sdk.get_or_insert_with(Controller::new);
+                state.lock().push_frame(Frame::deserialize(&data).ok());
+                for (_, listener) in &mut listeners {
+                    listener.on_frame(&controller);
+                }
+            }
+            Command::FrameData(data) => {
+                state.lock().push_frame_data(data);
+                for (_, listener) in &mut listeners {
+                    listener.on_frame(&controller);
+                }
+            }
+            Command::Images(images) => {
+                state.lock().images = images;
+                for (_, listener) in &mut listeners {
+                    listener.on_images(&controller);
+                }
+            }
+            Command::Sync(done) => {
+                done.send(()).ok();
+            }
+        }
+    }
+
+    for (_, listener) in &mut listeners {
+        listener.on_exit(&controller);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::data::fixtures;
+
+    fn frame(id: i64) -> FrameData {
+        fixtures::frame(id, Vec::new())
+    }
+
+    /// Records the callbacks it receives.
+    struct Log(Arc<Mutex<Vec<String>>>);
+
+    impl Log {
+        fn push(&self, entry: impl Into<String>) {
+            self.0.lock().unwrap().push(entry.into());
+        }
+    }
+
+    impl Listener for Log {
+        fn on_init(&mut self, _: &ControllerRef) {
+            self.push("init");
+        }
+
+        fn on_connect(&mut self, controller: &ControllerRef) {
+            assert!(controller.is_connected());
+            self.push("connect");
+        }
+
+        fn on_disconnect(&mut self, controller: &ControllerRef) {
+            assert!(!controller.is_connected());
+            self.push("disconnect");
+        }
+
+        fn on_frame(&mut self, controller: &ControllerRef) {
+            assert!(controller.frame().is_none());
+            let frame = controller.frame_data().unwrap();
+            let previous = controller.frame_data_at(1).map(|frame| frame.id);
+            self.push(format!("frame {} after {:?}", frame.id, previous));
+        }
+
+        fn on_exit(&mut self, _: &ControllerRef) {
+            self.push("exit");
+        }
+    }
+
+    #[test]
+    fn listener_lifecycle() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut mock = MockController::new();
+        // Register through the trait, like code shared with a real `Controller` would.
+        let handle = ControllerBackend::add_listener(&mut mock, Log(log.clone())).unwrap();
+
+        mock.connect();
+        mock.push_frame_data(frame(1));
+        mock.push_frame_data(frame(2));
+        mock.disconnect();
+        mock.remove_listener(handle).unwrap();
+        mock.push_frame_data(frame(3));
+        mock.wait_idle();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "init",
+                "connect",
+                "frame 1 after None",
+                "frame 2 after Some(1)",
+                "disconnect",
+                "exit",
+            ],
+        );
+        assert_eq!(mock.frame_data().unwrap(), frame(3));
+        assert_eq!(
+            mock.remove_listener(handle).err(),
+            Some(Error::UnknownListener)
+        );
+    }
+}
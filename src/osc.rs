@@ -0,0 +1,578 @@
+//! Output of tracking data as [OSC] messages.
+//!
+//! [`Bridge`] is a [`Listener`] that sends the palm positions, finger tips, pinch and grab
+//! strengths and gestures of every frame to a UDP address, which is what tools like Max/MSP and
+//! TouchDesigner consume. The OSC addresses are built from templates (see [`BridgeConfig`]).
+//!
+//! The [`Message`] and [`Bundle`] types implement the subset of OSC 1.0 needed for that, and may
+//! be used to send other data to the same receivers.
+//!
+//! The `leap-osc` binary runs a [`Bridge`] configured from its command line.
+//!
+//! [OSC]: https://opensoundcontrol.stanford.edu/spec-1_0.html
+
+use std::{
+    io,
+    net::{SocketAddr, ToSocketAddrs, UdpSocket},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    data::{FrameData, GestureDetails},
+    pointable::FingerType,
+    ControllerRef, GestureState, GestureType, Listener, Vector,
+};
+
+/// An OSC time tag, in the NTP format: seconds since 1900 in the upper 32 bits, fractions of a
+/// second in the lower 32 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeTag(pub u64);
+
+impl TimeTag {
+    /// The special time tag meaning "process immediately".
+    pub const IMMEDIATELY: TimeTag = TimeTag(1);
+
+    /// Seconds between the NTP epoch (1900) and the Unix epoch (1970).
+    const UNIX_OFFSET: u64 = 2_208_988_800;
+
+    /// Converts a [`SystemTime`] to a time tag.
+    ///
+    /// Times before the Unix epoch are clamped to it.
+    pub fn from_system_time(time: SystemTime) -> Self {
+        let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let seconds = since_epoch.as_secs() + Self::UNIX_OFFSET;
+        let fraction = (u64::from(since_epoch.subsec_nanos()) << 32) / 1_000_000_000;
+        TimeTag(seconds << 32 | fraction)
+    }
+
+    /// Returns the time tag of the current time.
+    pub fn now() -> Self {
+        Self::from_system_time(SystemTime::now())
+    }
+}
+
+/// An argument of an OSC [`Message`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Argument {
+    /// A 32-bit integer (type tag `i`).
+    Int(i32),
+    /// A 32-bit float (type tag `f`).
+    Float(f32),
+    /// A string (type tag `s`).
+    String(String),
+    /// A boolean (type tags `T` and `F`).
+    Bool(bool),
+    /// A time tag (type tag `t`).
+    Time(TimeTag),
+}
+
+impl Argument {
+    fn type_tag(&self) -> u8 {
+        match self {
+            Argument::Int(_) => b'i',
+            Argument::Float(_) => b'f',
+            Argument::String(_) => b's',
+            Argument::Bool(true) => b'T',
+            Argument::Bool(false) => b'F',
+            Argument::Time(_) => b't',
+        }
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Argument::Int(i) => out.extend_from_slice(&i.to_be_bytes()),
+            Argument::Float(f) => out.extend_from_slice(&f.to_be_bytes()),
+            Argument::String(s) => put_string(out, s.as_bytes()),
+            Argument::Bool(_) => {}
+            Argument::Time(time) => out.extend_from_slice(&time.0.to_be_bytes()),
+        }
+    }
+}
+
+impl From<i32> for Argument {
+    fn from(i: i32) -> Self {
+        Argument::Int(i)
+    }
+}
+
+impl From<f32> for Argument {
+    fn from(f: f32) -> Self {
+        Argument::Float(f)
+    }
+}
+
+impl From<&str> for Argument {
+    fn from(s: &str) -> Self {
+        Argument::String(s.to_string())
+    }
+}
+
+impl From<String> for Argument {
+    fn from(s: String) -> Self {
+        Argument::String(s)
+    }
+}
+
+impl From<bool> for Argument {
+    fn from(b: bool) -> Self {
+        Argument::Bool(b)
+    }
+}
+
+impl From<TimeTag> for Argument {
+    fn from(time: TimeTag) -> Self {
+        Argument::Time(time)
+    }
+}
+
+/// An OSC message: an address pattern and a list of arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub address: String,
+    pub args: Vec<Argument>,
+}
+
+impl Message {
+    /// Creates a message without arguments.
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Appends an argument to the message.
+    pub fn arg(mut self, arg: impl Into<Argument>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Appends the X, Y and Z coordinates of a vector as float arguments.
+    pub fn vector(self, vector: Vector) -> Self {
+        self.arg(vector.x).arg(vector.y).arg(vector.z)
+    }
+
+    /// Appends the binary representation of the message to `out`.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        put_string(out, self.address.as_bytes());
+
+        let mut tags = Vec::with_capacity(self.args.len() + 1);
+        tags.push(b',');
+        tags.extend(self.args.iter().map(Argument::type_tag));
+        put_string(out, &tags);
+
+        for arg in &self.args {
+            arg.encode(out);
+        }
+    }
+
+    /// Returns the binary representation of the message.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode(&mut out);
+        out
+    }
+}
+
+/// An OSC bundle of messages that are to be processed together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bundle {
+    pub time: TimeTag,
+    pub messages: Vec<Message>,
+}
+
+impl Bundle {
+    /// Creates an empty bundle.
+    pub fn new(time: TimeTag) -> Self {
+        Self {
+            time,
+            messages: Vec::new(),
+        }
+    }
+
+    /// Appends the binary representation of the bundle to `out`.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        put_string(out, b"#bundle");
+        out.extend_from_slice(&self.time.0.to_be_bytes());
+        for message in &self.messages {
+            let size_at = out.len();
+            out.extend_from_slice(&[0; 4]);
+            message.encode(out);
+            let size = (out.len() - size_at - 4) as u32;
+            out[size_at..size_at + 4].copy_from_slice(&size.to_be_bytes());
+        }
+    }
+
+    /// Returns the binary representation of the bundle.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode(&mut out);
+        out
+    }
+}
+
+/// Writes an OSC string: NUL-terminated and padded to a multiple of 4 bytes.
+fn put_string(out: &mut Vec<u8>, s: &[u8]) {
+    out.extend_from_slice(s);
+    let padding = 4 - s.len() % 4;
+    out.resize(out.len() + padding, 0);
+}
+
+/// The OSC addresses used by a [`Bridge`].
+///
+/// Each field is an address template, or `None` to not send the corresponding messages. The
+/// templates may contain the following placeholders:
+///
+/// - `{id}`: the ID of the hand or gesture.
+/// - `{side}`: `left` or `right`, for hands and fingers.
+/// - `{finger}`: `thumb`, `index`, `middle`, `ring` or `pinky`, for fingers.
+/// - `{type}`: `swipe`, `circle`, `screen_tap` or `key_tap`, for gestures.
+/// - `{state}`: `start`, `update` or `stop`, for gestures.
+#[derive(Debug, Clone)]
+pub struct BridgeConfig {
+    /// Address for the palm position of a hand, sent as 3 floats.
+    pub palm: Option<String>,
+    /// Address for the tip position of a finger, sent as 3 floats.
+    pub finger_tip: Option<String>,
+    /// Address for the pinch strength of a hand, sent as a float between 0 and 1.
+    pub pinch: Option<String>,
+    /// Address for the grab strength of a hand, sent as a float between 0 and 1.
+    pub grab: Option<String>,
+    /// Address for gesture events, sent as the gesture ID, its state and its position (the
+    /// current position of a swipe or tap, or the center of a circle) as 3 floats.
+    ///
+    /// When set, the bridge enables all [`GestureType`]s on the controller.
+    pub gesture: Option<String>,
+    /// Whether to normalize positions to the interaction box, so that they range from 0 to 1.
+    /// Otherwise, positions are sent in millimeters.
+    pub normalize: bool,
+}
+
+impl Default for BridgeConfig {
+    fn default() -> Self {
+        Self {
+            palm: Some("/leap/{side}/palm".into()),
+            finger_tip: Some("/leap/{side}/{finger}/tip".into()),
+            pinch: Some("/leap/{side}/pinch".into()),
+            grab: Some("/leap/{side}/grab".into()),
+            gesture: Some("/leap/gesture/{type}".into()),
+            normalize: false,
+        }
+    }
+}
+
+/// A [`Listener`] that sends the tracking data of every frame to an OSC receiver over UDP.
+///
+/// The messages of a frame are sent as a single [`Bundle`].
+pub struct Bridge {
+    socket: UdpSocket,
+    target: SocketAddr,
+    config: BridgeConfig,
+}
+
+impl Bridge {
+    /// Creates a bridge sending to `target`.
+    pub fn new(target: impl ToSocketAddrs, config: BridgeConfig) -> io::Result<Self> {
+        let (socket, target) = udp_socket(target)?;
+        Ok(Self {
+            socket,
+            target,
+            config,
+        })
+    }
+
+    /// Returns the OSC messages describing `frame`.
+    pub fn messages(&self, frame: &FrameData) -> Vec<Message> {
+        let config = &self.config;
+        let position = |position| {
+            if config.normalize {
+                frame.interaction_box.normalize_point(position, true)
+            } else {
+                position
+            }
+        };
+        let mut messages = Vec::new();
+
+        for hand in &frame.hands {
+            let id = hand.id.to_string();
+            let side = if hand.is_left { "left" } else { "right" };
+            let address = |template: &str| template.replace("{id}", &id).replace("{side}", side);
+
+            if let Some(template) = &config.palm {
+                messages.push(Message::new(address(template)).vector(position(hand.palm_position)));
+            }
+            if let Some(template) = &config.finger_tip {
+                for finger in &hand.fingers {
+                    let address =
+                        address(template).replace("{finger}", finger_name(finger.finger_type));
+                    messages.push(
+                        Message::new(address).vector(position(finger.pointable.tip_position)),
+                    );
+                }
+            }
+            if let Some(template) = &config.pinch {
+                messages.push(Message::new(address(template)).arg(hand.pinch_strength));
+            }
+            if let Some(template) = &config.grab {
+                messages.push(Message::new(address(template)).arg(hand.grab_strength));
+            }
+        }
+
+        if let Some(template) = &config.gesture {
+            for gesture in &frame.gestures {
+                let (type_name, gesture_position) = match &gesture.details {
+                    GestureDetails::Swipe { position, .. } => ("swipe", *position),
+                    GestureDetails::Circle { center, .. } => ("circle", *center),
+                    GestureDetails::ScreenTap { position, .. } => ("screen_tap", *position),
+                    GestureDetails::KeyTap { position, .. } => ("key_tap", *position),
+                };
+                let state = match gesture.state {
+                    GestureState::Start => "start",
+                    GestureState::Update => "update",
+                    GestureState::Stop => "stop",
+                };
+                let address = template
+                    .replace("{id}", &gesture.id.to_string())
+                    .replace("{type}", type_name)
+                    .replace("{state}", state);
+                messages.push(
+                    Message::new(address)
+                        .arg(gesture.id)
+                        .arg(state)
+                        .vector(position(gesture_position)),
+                );
+            }
+        }
+
+        messages
+    }
+}
+
+impl Listener for Bridge {
+    fn on_init(&mut self, controller: &ControllerRef) {
+        if self.config.gesture.is_some() {
+            for gesture in GestureType::ALL {
+                controller.enable_gesture(gesture);
+            }
+        }
+    }
+
+    fn on_frame(&mut self, controller: &ControllerRef) {
+        let frame = match controller.frame_data() {
+            Some(frame) => frame,
+            None => return,
+        };
+        let bundle = Bundle {
+            time: TimeTag::IMMEDIATELY,
+            messages: self.messages(&frame),
+        };
+        if bundle.messages.is_empty() {
+            return;
+        }
+
+        send(&self.socket, self.target, &bundle);
+    }
+}
+
+/// Creates a UDP socket for sending to `target`, and resolves `target`.
+pub(crate) fn udp_socket(target: impl ToSocketAddrs) -> io::Result<(UdpSocket, SocketAddr)> {
+    let target = target
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no address to send OSC to"))?;
+    let socket = match target {
+        SocketAddr::V4(_) => UdpSocket::bind(("0.0.0.0", 0))?,
+        SocketAddr::V6(_) => UdpSocket::bind(("::", 0))?,
+    };
+    Ok((socket, target))
+}
+
+/// Sends `bundle` as a single datagram.
+pub(crate) fn send(socket: &UdpSocket, target: SocketAddr, bundle: &Bundle) {
+    // Failures are expected while nothing is listening on the target, so don't be noisy.
+    if let Err(e) = socket.send_to(&bundle.to_bytes(), target) {
+        log::debug!("failed to send OSC bundle to {}: {}", target, e);
+    }
+}
+
+fn finger_name(finger_type: FingerType) -> &'static str {
+    match finger_type {
+        FingerType::Thumb => "thumb",
+        FingerType::Index => "index",
+        FingerType::Middle => "middle",
+        FingerType::Ring => "ring",
+        FingerType::Pinky => "pinky",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::data::{fixtures, GestureData};
+
+    #[test]
+    fn strings_are_padded() {
+        for (s, len) in [("", 4), ("abc", 4), ("abcd", 8), ("abcde", 8)] {
+            let mut out = Vec::new();
+            put_string(&mut out, s.as_bytes());
+            assert_eq!(out.len(), len, "{:?}", s);
+            assert_eq!(&out[..s.len()], s.as_bytes());
+            assert!(out[s.len()..].iter().all(|b| *b == 0));
+        }
+    }
+
+    /// The first example message of the OSC 1.0 specification.
+    #[test]
+    fn float_message() {
+        let message = Message::new("/oscillator/4/frequency").arg(440.0);
+        let mut expected = b"/oscillator/4/frequency\0,f\0\0".to_vec();
+        expected.extend_from_slice(&[0x43, 0xdc, 0x00, 0x00]);
+        assert_eq!(message.to_bytes(), expected);
+    }
+
+    /// The second example message of the OSC 1.0 specification.
+    #[test]
+    fn mixed_message() {
+        let message = Message::new("/foo")
+            .arg(1000)
+            .arg(-1)
+            .arg("hello")
+            .arg(1.234)
+            .arg(5.678);
+        let expected = [
+            b"/foo\0\0\0\0".as_slice(),
+            b",iisff\0\0",
+            &[0x00, 0x00, 0x03, 0xe8],
+            &[0xff, 0xff, 0xff, 0xff],
+            b"hello\0\0\0",
+            &[0x3f, 0x9d, 0xf3, 0xb6],
+            &[0x40, 0xb5, 0xb2, 0x2d],
+        ]
+        .concat();
+        assert_eq!(message.to_bytes(), expected);
+    }
+
+    #[test]
+    fn bools_have_no_payload() {
+        let message = Message::new("/b").arg(true).arg(false).arg(1);
+        assert_eq!(message.to_bytes(), b"/b\0\0,TFi\0\0\0\0\0\0\0\x01");
+    }
+
+    #[test]
+    fn bundle() {
+        let mut bundle = Bundle::new(TimeTag::IMMEDIATELY);
+        bundle.messages.push(Message::new("/a").arg(1));
+        bundle.messages.push(Message::new("/bcde"));
+        let expected = [
+            b"#bundle\0".as_slice(),
+            &[0, 0, 0, 0, 0, 0, 0, 1],
+            &[0, 0, 0, 12],
+            b"/a\0\0,i\0\0\0\0\0\x01",
+            &[0, 0, 0, 12],
+            b"/bcde\0\0\0,\0\0\0",
+        ]
+        .concat();
+        assert_eq!(bundle.to_bytes(), expected);
+    }
+
+    #[test]
+    fn time_tag() {
+        assert_eq!(
+            TimeTag::from_system_time(UNIX_EPOCH),
+            TimeTag(2_208_988_800 << 32),
+        );
+        assert_eq!(
+            TimeTag::from_system_time(UNIX_EPOCH + Duration::from_millis(1500)),
+            TimeTag(2_208_988_801 << 32 | 0x8000_0000),
+        );
+        let before_epoch = UNIX_EPOCH - Duration::from_secs(1);
+        assert_eq!(
+            TimeTag::from_system_time(before_epoch),
+            TimeTag::from_system_time(UNIX_EPOCH),
+        );
+    }
+
+    fn bridge(config: BridgeConfig) -> Bridge {
+        Bridge::new("127.0.0.1:9000", config).unwrap()
+    }
+
+    fn frame() -> FrameData {
+        let hand = fixtures::hand(3, true, Vector::new(0.0, 200.0, 0.0));
+        let mut frame = fixtures::frame(1, vec![hand]);
+        frame.gestures.push(GestureData {
+            id: 7,
+            state: GestureState::Stop,
+            duration: Duration::ZERO,
+            hand_ids: vec![3],
+            pointable_ids: vec![31],
+            details: GestureDetails::KeyTap {
+                position: Vector::new(1.0, 2.0, 3.0),
+                direction: Vector::DOWN,
+                progress: 1.0,
+                pointable_id: Some(31),
+            },
+        });
+        frame
+    }
+
+    fn addresses(messages: &[Message]) -> Vec<&str> {
+        messages.iter().map(|m| &*m.address).collect()
+    }
+
+    #[test]
+    fn default_addresses() {
+        let messages = bridge(BridgeConfig::default()).messages(&frame());
+        assert_eq!(
+            addresses(&messages),
+            [
+                "/leap/left/palm",
+                "/leap/left/thumb/tip",
+                "/leap/left/index/tip",
+                "/leap/left/middle/tip",
+                "/leap/left/ring/tip",
+                "/leap/left/pinky/tip",
+                "/leap/left/pinch",
+                "/leap/left/grab",
+                "/leap/gesture/key_tap",
+            ],
+        );
+        assert_eq!(
+            messages[0].args,
+            [Argument::Float(0.0), 200.0.into(), 0.0.into()],
+        );
+        assert_eq!(
+            messages[8].args,
+            [
+                Argument::Int(7),
+                "stop".into(),
+                1.0.into(),
+                2.0.into(),
+                3.0.into()
+            ],
+        );
+    }
+
+    #[test]
+    fn templates() {
+        let messages = bridge(BridgeConfig {
+            palm: Some("/hand/{id}/{side}".into()),
+            finger_tip: None,
+            pinch: None,
+            grab: Some("/grab".into()),
+            gesture: Some("/{type}/{id}/{state}".into()),
+            normalize: true,
+        })
+        .messages(&frame());
+        assert_eq!(
+            addresses(&messages),
+            ["/hand/3/left", "/grab", "/key_tap/7/stop"]
+        );
+        // The palm is at the center of the interaction box.
+        assert_eq!(
+            messages[0].args,
+            [Argument::Float(0.5), 0.5.into(), 0.5.into()],
+        );
+    }
+}
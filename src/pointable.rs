@@ -0,0 +1,352 @@
+//! Fingers, tools, and the bones making up a finger.
+
+use std::{mem::MaybeUninit, ops::Deref, time::Duration};
+
+use crate::{ffi, hand::Hand, list::ffi_list, sys, Matrix, Vector};
+
+ffi_list! {
+    /// A list of [`Pointable`]s.
+    pub struct PointableList(sys::Leap_PointableList) {
+        count: sys::Leap_PointableList_count,
+        index: ffi::Leap_PointableList_operator_index,
+    }
+
+    /// An iterator over the [`Pointable`]s in a [`PointableList`].
+    pub struct PointableListIterator -> Pointable;
+}
+
+ffi_list! {
+    /// A list of [`Finger`]s.
+    pub struct FingerList(sys::Leap_FingerList) {
+        count: sys::Leap_FingerList_count,
+        index: ffi::Leap_FingerList_operator_index,
+    }
+
+    /// An iterator over the [`Finger`]s in a [`FingerList`].
+    pub struct FingerListIterator -> Finger;
+}
+
+/// A finger or tool.
+///
+/// All positions are given in millimeters, relative to the Leap Motion Controller.
+pub struct Pointable {
+    inner: Box<sys::Leap_Pointable>,
+}
+
+impl Pointable {
+    pub(crate) fn from_raw(inner: Box<sys::Leap_Pointable>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the pointable's unique ID.
+    ///
+    /// The ID stays the same across frames as long as the pointable stays tracked.
+    pub fn id(&self) -> i32 {
+        unsafe { sys::Leap_Pointable_id(&*self.inner) }
+    }
+
+    /// Returns the hand this pointable is attached to.
+    ///
+    /// Returns `None` for tools, and for fingers whose hand is not tracked.
+    pub fn hand(&self) -> Option<Hand> {
+        let hand = unsafe {
+            let mut hand = Box::new(MaybeUninit::uninit());
+            sys::Leap_Pointable_hand(hand.as_mut_ptr(), &*self.inner);
+            Hand::from_raw(crate::init_box(hand))
+        };
+        hand.is_valid().then_some(hand)
+    }
+
+    /// Returns the position of the tip.
+    pub fn tip_position(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Pointable_tipPosition(&*self.inner) })
+    }
+
+    /// Returns the tip position with additional smoothing and stabilization applied.
+    pub fn stabilized_tip_position(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Pointable_stabilizedTipPosition(&*self.inner) })
+    }
+
+    /// Returns the velocity of the tip, in millimeters per second.
+    pub fn tip_velocity(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Pointable_tipVelocity(&*self.inner) })
+    }
+
+    /// Returns the unit vector pointing in the same direction as the tip.
+    pub fn direction(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Pointable_direction(&*self.inner) })
+    }
+
+    /// Returns the estimated width of the pointable.
+    pub fn width(&self) -> f32 {
+        unsafe { sys::Leap_Pointable_width(&*self.inner) }
+    }
+
+    /// Returns the estimated length of the visible part of the pointable.
+    pub fn length(&self) -> f32 {
+        unsafe { sys::Leap_Pointable_length(&*self.inner) }
+    }
+
+    /// Returns whether this pointable is a finger.
+    pub fn is_finger(&self) -> bool {
+        unsafe { sys::Leap_Pointable_isFinger(&*self.inner) }
+    }
+
+    /// Returns whether this pointable is a tool.
+    pub fn is_tool(&self) -> bool {
+        unsafe { sys::Leap_Pointable_isTool(&*self.inner) }
+    }
+
+    /// Returns whether this pointable is extended (ie. a straight finger).
+    pub fn is_extended(&self) -> bool {
+        unsafe { sys::Leap_Pointable_isExtended(&*self.inner) }
+    }
+
+    /// Returns the touch zone the pointable is currently in.
+    pub fn touch_zone(&self) -> TouchZone {
+        let raw = unsafe { sys::Leap_Pointable_touchZone(&*self.inner) };
+        TouchZone::from_raw(raw)
+    }
+
+    /// Returns the normalized distance to the virtual touch plane.
+    ///
+    /// The distance ranges from +1.0 (at the far edge of the hovering zone) to 0.0 (touching the
+    /// touch plane) to -1.0 (fully penetrating the touch plane).
+    pub fn touch_distance(&self) -> f32 {
+        unsafe { sys::Leap_Pointable_touchDistance(&*self.inner) }
+    }
+
+    /// Returns how long this pointable has been continuously tracked.
+    pub fn time_visible(&self) -> Duration {
+        let secs = unsafe { sys::Leap_Pointable_timeVisible(&*self.inner) };
+        Duration::from_secs_f32(secs.max(0.0))
+    }
+
+    /// Returns whether this object contains valid tracking data.
+    pub(crate) fn is_valid(&self) -> bool {
+        unsafe { sys::Leap_Pointable_isValid(&*self.inner) }
+    }
+}
+
+impl Drop for Pointable {
+    fn drop(&mut self) {
+        // No `Pointable` destructor, call superclass dtor instead.
+        unsafe {
+            sys::Leap_Interface_Interface_destructor((&mut *self.inner) as *mut _ as _);
+        }
+    }
+}
+
+/// A tracked finger.
+///
+/// [`Finger`] dereferences to [`Pointable`], which provides the tip position, direction, etc.
+pub struct Finger {
+    pointable: Pointable,
+}
+
+impl Finger {
+    pub(crate) fn from_raw(inner: Box<sys::Leap_Finger>) -> Self {
+        // `Leap::Finger` is a `Leap::Pointable` that adds no fields of its own, so the allocation
+        // can be shared.
+        let inner = unsafe { Box::from_raw(Box::into_raw(inner) as *mut sys::Leap_Pointable) };
+        Self {
+            pointable: Pointable::from_raw(inner),
+        }
+    }
+
+    fn raw(&self) -> *const sys::Leap_Finger {
+        &*self.pointable.inner as *const _ as *const _
+    }
+
+    /// Returns the anatomical type of this finger, or `None` if the SDK reports a type this
+    /// library does not know.
+    pub fn finger_type(&self) -> Option<FingerType> {
+        let raw = unsafe { sys::Leap_Finger_type(self.raw()) };
+        FingerType::from_raw(raw)
+    }
+
+    /// Returns the position of one of the finger's joints.
+    pub fn joint_position(&self, joint: Joint) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Finger_jointPosition(self.raw(), joint as u32) })
+    }
+
+    /// Returns one of the bones making up this finger.
+    pub fn bone(&self, bone: BoneType) -> Bone {
+        unsafe {
+            let mut raw = Box::new(MaybeUninit::uninit());
+            sys::Leap_Finger_bone(raw.as_mut_ptr(), self.raw(), bone as u32);
+            Bone {
+                inner: crate::init_box(raw),
+            }
+        }
+    }
+
+    /// Returns all 4 bones of this finger, ordered from the wrist towards the tip.
+    ///
+    /// Thumbs have a zero-length metacarpal bone to keep their structure consistent with the other
+    /// fingers.
+    pub fn bones(&self) -> [Bone; 4] {
+        BoneType::ALL.map(|ty| self.bone(ty))
+    }
+}
+
+impl Deref for Finger {
+    type Target = Pointable;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pointable
+    }
+}
+
+/// A bone in a [`Finger`].
+pub struct Bone {
+    inner: Box<sys::Leap_Bone>,
+}
+
+impl Bone {
+    /// Returns the position of the joint at the base of the bone (closer to the wrist).
+    pub fn prev_joint(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Bone_prevJoint(&*self.inner) })
+    }
+
+    /// Returns the position of the joint at the end of the bone (closer to the finger tip).
+    pub fn next_joint(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Bone_nextJoint(&*self.inner) })
+    }
+
+    /// Returns the midpoint of the bone.
+    pub fn center(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Bone_center(&*self.inner) })
+    }
+
+    /// Returns the unit vector pointing from the base of the bone towards its end.
+    pub fn direction(&self) -> Vector {
+        Vector::from_raw(unsafe { sys::Leap_Bone_direction(&*self.inner) })
+    }
+
+    /// Returns the length of the bone.
+    pub fn length(&self) -> f32 {
+        unsafe { sys::Leap_Bone_length(&*self.inner) }
+    }
+
+    /// Returns the estimated width of the flesh around the bone.
+    pub fn width(&self) -> f32 {
+        unsafe { sys::Leap_Bone_width(&*self.inner) }
+    }
+
+    /// Returns the anatomical type of this bone, or `None` if the SDK reports a type this library
+    /// does not know.
+    pub fn bone_type(&self) -> Option<BoneType> {
+        let raw = unsafe { sys::Leap_Bone_type(&*self.inner) };
+        BoneType::from_raw(raw)
+    }
+
+    /// Returns the orientation of the bone as a basis matrix.
+    pub fn basis(&self) -> Matrix {
+        Matrix::from_raw(unsafe { sys::Leap_Bone_basis(&*self.inner) })
+    }
+}
+
+impl Drop for Bone {
+    fn drop(&mut self) {
+        // No `Bone` destructor, call superclass dtor instead.
+        unsafe {
+            sys::Leap_Interface_Interface_destructor((&mut *self.inner) as *mut _ as _);
+        }
+    }
+}
+
+/// The anatomical type of a [`Finger`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FingerType {
+    Thumb = sys::Leap_Finger_Type_TYPE_THUMB,
+    Index = sys::Leap_Finger_Type_TYPE_INDEX,
+    Middle = sys::Leap_Finger_Type_TYPE_MIDDLE,
+    Ring = sys::Leap_Finger_Type_TYPE_RING,
+    Pinky = sys::Leap_Finger_Type_TYPE_PINKY,
+}
+
+impl FingerType {
+    fn from_raw(raw: sys::Leap_Finger_Type) -> Option<Self> {
+        match raw {
+            sys::Leap_Finger_Type_TYPE_THUMB => Some(Self::Thumb),
+            sys::Leap_Finger_Type_TYPE_INDEX => Some(Self::Index),
+            sys::Leap_Finger_Type_TYPE_MIDDLE => Some(Self::Middle),
+            sys::Leap_Finger_Type_TYPE_RING => Some(Self::Ring),
+            sys::Leap_Finger_Type_TYPE_PINKY => Some(Self::Pinky),
+            _ => None,
+        }
+    }
+}
+
+/// The anatomical type of a [`Bone`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoneType {
+    /// The bone inside the palm connecting the finger to the wrist.
+    Metacarpal = sys::Leap_Bone_Type_TYPE_METACARPAL,
+    Proximal = sys::Leap_Bone_Type_TYPE_PROXIMAL,
+    Intermediate = sys::Leap_Bone_Type_TYPE_INTERMEDIATE,
+    /// The bone at the tip of the finger.
+    Distal = sys::Leap_Bone_Type_TYPE_DISTAL,
+}
+
+impl BoneType {
+    /// All bone types, ordered from the wrist towards the finger tip.
+    pub const ALL: [Self; 4] = [
+        Self::Metacarpal,
+        Self::Proximal,
+        Self::Intermediate,
+        Self::Distal,
+    ];
+
+    fn from_raw(raw: sys::Leap_Bone_Type) -> Option<Self> {
+        match raw {
+            sys::Leap_Bone_Type_TYPE_METACARPAL => Some(Self::Metacarpal),
+            sys::Leap_Bone_Type_TYPE_PROXIMAL => Some(Self::Proximal),
+            sys::Leap_Bone_Type_TYPE_INTERMEDIATE => Some(Self::Intermediate),
+            sys::Leap_Bone_Type_TYPE_DISTAL => Some(Self::Distal),
+            _ => None,
+        }
+    }
+}
+
+/// A joint in a [`Finger`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Joint {
+    /// The metacarpophalangeal joint, or knuckle.
+    Mcp = sys::Leap_Finger_Joint_JOINT_MCP,
+    /// The proximal interphalangeal joint.
+    Pip = sys::Leap_Finger_Joint_JOINT_PIP,
+    /// The distal interphalangeal joint.
+    Dip = sys::Leap_Finger_Joint_JOINT_DIP,
+    /// The tip of the finger.
+    Tip = sys::Leap_Finger_Joint_JOINT_TIP,
+}
+
+/// The zone a [`Pointable`] is in, relative to the virtual touch plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TouchZone {
+    /// The pointable is too far away from the touch plane to be considered.
+    None,
+    /// The pointable is close to, but not touching, the touch plane.
+    Hovering,
+    /// The pointable has penetrated the touch plane.
+    Touching,
+}
+
+impl TouchZone {
+    fn from_raw(raw: sys::Leap_Pointable_Zone) -> Self {
+        match raw {
+            sys::Leap_Pointable_Zone_ZONE_HOVERING => Self::Hovering,
+            sys::Leap_Pointable_Zone_ZONE_TOUCHING => Self::Touching,
+            _ => Self::None,
+        }
+    }
+}
@@ -0,0 +1,1199 @@
+//! Gesture recognizers implemented in Rust.
+//!
+//! Unlike the gestures detected by the Leap Motion service (see [`gesture`][crate::gesture]),
+//! these recognizers run inside the application and can be tuned through their configuration.
+//! They operate on plain [`HandSnapshot`]s rather than on SDK objects, so they can also be driven
+//! by synthetic data.
+//!
+//! Every recognizer tracks a single hand: feed it one snapshot of that hand per frame via
+//! [`Recognizer::update`], and call [`Recognizer::reset`] once the hand is lost. When the hand ID
+//! changes, a gesture in progress is stopped before the new hand is considered, and any progress
+//! the previous hand made towards a gesture is discarded.
+
+use std::{
+    collections::VecDeque,
+    f32::consts::{FRAC_PI_6, PI, TAU},
+    time::Duration,
+};
+
+use crate::{
+    data::{FingerData, FrameData, HandData},
+    hand::Hand,
+    pointable::{Finger, FingerType},
+    Frame, GestureState, Timestamp, Vector,
+};
+
+/// The tracking data of a single [`Hand`], as consumed by the recognizers.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HandSnapshot {
+    /// The time at which the hand was observed.
+    pub timestamp: Timestamp,
+    /// The hand's ID, see [`Hand::id`].
+    pub id: i32,
+    pub is_left: bool,
+    pub palm_position: Vector,
+    /// The velocity of the palm, in millimeters per second.
+    pub palm_velocity: Vector,
+    pub palm_normal: Vector,
+    pub direction: Vector,
+    pub pinch_strength: f32,
+    pub grab_strength: f32,
+    /// The fingers of the hand, indexed by [`FingerType`].
+    pub fingers: [FingerSnapshot; 5],
+}
+
+impl HandSnapshot {
+    /// Captures the current state of `hand`, observed at `timestamp`.
+    pub fn from_hand(hand: &Hand, timestamp: Timestamp) -> Self {
+        let mut fingers = [FingerSnapshot::default(); 5];
+        for finger in hand.fingers().iter() {
+            if let Some(finger_type) = finger.finger_type() {
+                fingers[finger_type as usize] = FingerSnapshot::from_finger(&finger);
+            }
+        }
+
+        Self {
+            timestamp,
+            id: hand.id(),
+            is_left: hand.is_left(),
+            palm_position: hand.palm_position(),
+            palm_velocity: hand.palm_velocity(),
+            palm_normal: hand.palm_normal(),
+            direction: hand.direction(),
+            pinch_strength: hand.pinch_strength(),
+            grab_strength: hand.grab_strength(),
+            fingers,
+        }
+    }
+
+    /// Captures all hands in `frame`.
+    pub fn from_frame(frame: &Frame) -> Vec<Self> {
+        let timestamp = frame.timestamp();
+        frame
+            .hands()
+            .iter()
+            .map(|hand| Self::from_hand(&hand, timestamp))
+            .collect()
+    }
+
+    /// Captures the state of a copied hand, observed at `timestamp`.
+    pub fn from_hand_data(hand: &HandData, timestamp: Timestamp) -> Self {
+        let mut fingers = [FingerSnapshot::default(); 5];
+        for finger in &hand.fingers {
+            fingers[finger.finger_type as usize] = FingerSnapshot::from_finger_data(finger);
+        }
+
+        Self {
+            timestamp,
+            id: hand.id,
+            is_left: hand.is_left,
+            palm_position: hand.palm_position,
+            palm_velocity: hand.palm_velocity,
+            palm_normal: hand.palm_normal,
+            direction: hand.direction,
+            pinch_strength: hand.pinch_strength,
+            grab_strength: hand.grab_strength,
+            fingers,
+        }
+    }
+
+    /// Captures all hands in a copied frame.
+    pub fn from_frame_data(frame: &FrameData) -> Vec<Self> {
+        frame
+            .hands
+            .iter()
+            .map(|hand| Self::from_hand_data(hand, frame.timestamp))
+            .collect()
+    }
+
+    /// Returns the snapshot of the finger of the given type.
+    pub fn finger(&self, finger: FingerType) -> &FingerSnapshot {
+        &self.fingers[finger as usize]
+    }
+}
+
+/// The tracking data of a single [`Finger`], as part of a [`HandSnapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FingerSnapshot {
+    pub tip_position: Vector,
+    /// The velocity of the finger tip, in millimeters per second.
+    pub tip_velocity: Vector,
+    pub direction: Vector,
+    pub is_extended: bool,
+}
+
+impl FingerSnapshot {
+    /// Captures the current state of `finger`.
+    pub fn from_finger(finger: &Finger) -> Self {
+        Self {
+            tip_position: finger.tip_position(),
+            tip_velocity: finger.tip_velocity(),
+            direction: finger.direction(),
+            is_extended: finger.is_extended(),
+        }
+    }
+
+    /// Captures the state of a copied finger.
+    pub fn from_finger_data(finger: &FingerData) -> Self {
+        Self {
+            tip_position: finger.pointable.tip_position,
+            tip_velocity: finger.pointable.tip_velocity,
+            direction: finger.pointable.direction,
+            is_extended: finger.pointable.is_extended,
+        }
+    }
+}
+
+/// A stateful gesture recognizer, fed with the snapshots of a single hand.
+pub trait Recognizer {
+    /// The type of event emitted by the recognizer.
+    type Event;
+
+    /// Processes the next snapshot of the tracked hand.
+    ///
+    /// Returns an event if a gesture started, continued, or ended with this snapshot.
+    fn update(&mut self, hand: &HandSnapshot) -> Option<Self::Event>;
+
+    /// Resets the recognizer, eg. because the tracked hand was lost.
+    ///
+    /// If a gesture was in progress, the event ending it is returned.
+    fn reset(&mut self) -> Option<Self::Event>;
+}
+
+/// An event emitted by one of the recognizers in this module.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Event<T> {
+    pub state: GestureState,
+    /// The ID of the hand performing the gesture.
+    pub hand_id: i32,
+    /// The timestamp of the snapshot that caused this event.
+    pub timestamp: Timestamp,
+    /// Gesture-specific data.
+    pub data: T,
+}
+
+/// Tracks the hand fed to a recognizer and the last event of a continuous gesture, to derive the
+/// states of the following ones.
+#[derive(Debug)]
+struct Tracker<T> {
+    /// The ID of the hand seen by the last update since the last reset.
+    hand_id: Option<i32>,
+    last: Option<Event<T>>,
+}
+
+impl<T: Copy> Tracker<T> {
+    fn new() -> Self {
+        Self {
+            hand_id: None,
+            last: None,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.last.is_some()
+    }
+
+    /// Returns whether the previous updates saw a different hand than `hand`.
+    ///
+    /// The recognizer has to be reset before it considers `hand`, so that no progress of the
+    /// previous hand carries over.
+    fn is_other_hand(&self, hand: &HandSnapshot) -> bool {
+        matches!(self.hand_id, Some(id) if id != hand.id)
+    }
+
+    /// Records that `hand` is the hand being tracked.
+    fn track(&mut self, hand: &HandSnapshot) {
+        self.hand_id = Some(hand.id);
+    }
+
+    /// Emits a `Start` or `Update` event, depending on whether the gesture is in progress.
+    fn emit(&mut self, hand: &HandSnapshot, data: T) -> Event<T> {
+        let state = if self.is_active() {
+            GestureState::Update
+        } else {
+            GestureState::Start
+        };
+        let event = Event {
+            state,
+            hand_id: hand.id,
+            timestamp: hand.timestamp,
+            data,
+        };
+        self.last = Some(event);
+        event
+    }
+
+    /// Ends the gesture in progress, repeating the data of its last event.
+    fn stop(&mut self) -> Option<Event<T>> {
+        self.last.take().map(|last| Event {
+            state: GestureState::Stop,
+            ..last
+        })
+    }
+
+    /// Ends the gesture in progress like [`Tracker::stop`], and forgets the tracked hand.
+    fn reset(&mut self) -> Option<Event<T>> {
+        self.hand_id = None;
+        self.stop()
+    }
+}
+
+/// Returns the number of seconds between two timestamps.
+fn seconds_between(earlier: Timestamp, later: Timestamp) -> f32 {
+    (later.as_raw() - earlier.as_raw()) as f32 / 1_000_000.0
+}
+
+/// Configuration of a [`PinchRecognizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PinchConfig {
+    /// The [`HandSnapshot::pinch_strength`] at which a pinch starts.
+    pub start_strength: f32,
+    /// The pinch strength below which a pinch ends.
+    ///
+    /// Should be lower than `start_strength`, to prevent jitter around the threshold.
+    pub stop_strength: f32,
+}
+
+impl Default for PinchConfig {
+    fn default() -> Self {
+        Self {
+            start_strength: 0.8,
+            stop_strength: 0.6,
+        }
+    }
+}
+
+/// Data of a pinch gesture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pinch {
+    pub strength: f32,
+    /// The midpoint between the tips of thumb and index finger.
+    pub position: Vector,
+}
+
+/// Recognizes pinching with thumb and index finger.
+#[derive(Debug)]
+pub struct PinchRecognizer {
+    config: PinchConfig,
+    tracker: Tracker<Pinch>,
+}
+
+impl PinchRecognizer {
+    pub fn new(config: PinchConfig) -> Self {
+        Self {
+            config,
+            tracker: Tracker::new(),
+        }
+    }
+}
+
+impl Default for PinchRecognizer {
+    fn default() -> Self {
+        Self::new(PinchConfig::default())
+    }
+}
+
+impl Recognizer for PinchRecognizer {
+    type Event = Event<Pinch>;
+
+    fn update(&mut self, hand: &HandSnapshot) -> Option<Self::Event> {
+        if self.tracker.is_other_hand(hand) {
+            if let Some(event) = self.reset() {
+                return Some(event);
+            }
+        }
+        self.tracker.track(hand);
+
+        let threshold = if self.tracker.is_active() {
+            self.config.stop_strength
+        } else {
+            self.config.start_strength
+        };
+        if hand.pinch_strength < threshold {
+            return self.tracker.stop();
+        }
+
+        let thumb = hand.finger(FingerType::Thumb).tip_position;
+        let index = hand.finger(FingerType::Index).tip_position;
+        let data = Pinch {
+            strength: hand.pinch_strength,
+            position: (thumb + index) / 2.0,
+        };
+        Some(self.tracker.emit(hand, data))
+    }
+
+    fn reset(&mut self) -> Option<Self::Event> {
+        self.tracker.reset()
+    }
+}
+
+/// Configuration of a [`GrabRecognizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrabConfig {
+    /// The [`HandSnapshot::grab_strength`] at which a grab starts.
+    pub start_strength: f32,
+    /// The grab strength below which a grab ends.
+    ///
+    /// Should be lower than `start_strength`, to prevent jitter around the threshold.
+    pub stop_strength: f32,
+}
+
+impl Default for GrabConfig {
+    fn default() -> Self {
+        Self {
+            start_strength: 0.9,
+            stop_strength: 0.7,
+        }
+    }
+}
+
+/// Data of a grab gesture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Grab {
+    pub strength: f32,
+    /// The palm position of the grabbing hand.
+    pub position: Vector,
+}
+
+/// Recognizes closing the hand to a fist.
+#[derive(Debug)]
+pub struct GrabRecognizer {
+    config: GrabConfig,
+    tracker: Tracker<Grab>,
+}
+
+impl GrabRecognizer {
+    pub fn new(config: GrabConfig) -> Self {
+        Self {
+            config,
+            tracker: Tracker::new(),
+        }
+    }
+}
+
+impl Default for GrabRecognizer {
+    fn default() -> Self {
+        Self::new(GrabConfig::default())
+    }
+}
+
+impl Recognizer for GrabRecognizer {
+    type Event = Event<Grab>;
+
+    fn update(&mut self, hand: &HandSnapshot) -> Option<Self::Event> {
+        if self.tracker.is_other_hand(hand) {
+            if let Some(event) = self.reset() {
+                return Some(event);
+            }
+        }
+        self.tracker.track(hand);
+
+        let threshold = if self.tracker.is_active() {
+            self.config.stop_strength
+        } else {
+            self.config.start_strength
+        };
+        if hand.grab_strength < threshold {
+            return self.tracker.stop();
+        }
+
+        let data = Grab {
+            strength: hand.grab_strength,
+            position: hand.palm_position,
+        };
+        Some(self.tracker.emit(hand, data))
+    }
+
+    fn reset(&mut self) -> Option<Self::Event> {
+        self.tracker.reset()
+    }
+}
+
+/// Configuration of a [`SwipeRecognizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwipeConfig {
+    /// The minimum palm speed, in millimeters per second.
+    pub min_speed: f32,
+    /// The distance the palm has to travel before a swipe starts, in millimeters.
+    pub min_length: f32,
+}
+
+impl Default for SwipeConfig {
+    fn default() -> Self {
+        // Same defaults as the service's swipe gesture.
+        Self {
+            min_speed: 1000.0,
+            min_length: 150.0,
+        }
+    }
+}
+
+/// The main direction of a swipe, along one of the coordinate axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+    /// Away from the user.
+    Forward,
+    /// Towards the user.
+    Backward,
+}
+
+impl SwipeDirection {
+    /// Returns the axis-aligned direction closest to `direction`.
+    pub fn from_vector(direction: Vector) -> Self {
+        let (x, y, z) = (direction.x.abs(), direction.y.abs(), direction.z.abs());
+        if x >= y && x >= z {
+            if direction.x < 0.0 {
+                Self::Left
+            } else {
+                Self::Right
+            }
+        } else if y >= z {
+            if direction.y < 0.0 {
+                Self::Down
+            } else {
+                Self::Up
+            }
+        } else if direction.z < 0.0 {
+            Self::Forward
+        } else {
+            Self::Backward
+        }
+    }
+}
+
+/// Data of a swipe gesture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Swipe {
+    /// The palm position at which the swipe started.
+    pub start_position: Vector,
+    /// The current palm position.
+    pub position: Vector,
+    /// The unit vector pointing from `start_position` to `position`.
+    pub direction: Vector,
+    /// The current palm speed, in millimeters per second.
+    pub speed: f32,
+}
+
+impl Swipe {
+    /// Returns the axis-aligned direction closest to [`Swipe::direction`].
+    pub fn main_direction(&self) -> SwipeDirection {
+        SwipeDirection::from_vector(self.direction)
+    }
+}
+
+/// Recognizes fast, straight movements of the whole hand, in any direction.
+#[derive(Debug)]
+pub struct SwipeRecognizer {
+    config: SwipeConfig,
+    start_position: Option<Vector>,
+    tracker: Tracker<Swipe>,
+}
+
+impl SwipeRecognizer {
+    pub fn new(config: SwipeConfig) -> Self {
+        Self {
+            config,
+            start_position: None,
+            tracker: Tracker::new(),
+        }
+    }
+}
+
+impl Default for SwipeRecognizer {
+    fn default() -> Self {
+        Self::new(SwipeConfig::default())
+    }
+}
+
+impl Recognizer for SwipeRecognizer {
+    type Event = Event<Swipe>;
+
+    fn update(&mut self, hand: &HandSnapshot) -> Option<Self::Event> {
+        if self.tracker.is_other_hand(hand) {
+            if let Some(event) = self.reset() {
+                return Some(event);
+            }
+        }
+        self.tracker.track(hand);
+
+        let speed = hand.palm_velocity.magnitude();
+        if speed < self.config.min_speed {
+            return self.reset();
+        }
+
+        let start_position = *self.start_position.get_or_insert(hand.palm_position);
+        let travelled = hand.palm_position - start_position;
+        if !self.tracker.is_active() && travelled.magnitude() < self.config.min_length {
+            return None;
+        }
+
+        let data = Swipe {
+            start_position,
+            position: hand.palm_position,
+            direction: travelled.normalized(),
+            speed,
+        };
+        Some(self.tracker.emit(hand, data))
+    }
+
+    fn reset(&mut self) -> Option<Self::Event> {
+        self.start_position = None;
+        self.tracker.reset()
+    }
+}
+
+/// Configuration of a [`CircleRecognizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircleConfig {
+    /// The finger tracing the circle.
+    pub finger: FingerType,
+    /// The minimum circle radius, in millimeters.
+    pub min_radius: f32,
+    /// The arc (in radians) that has to be traced before a circle starts.
+    pub min_arc: f32,
+    /// The minimum finger tip speed, in millimeters per second.
+    pub min_speed: f32,
+    /// How far back the finger tip positions are used to fit the circle.
+    pub window: Duration,
+}
+
+impl Default for CircleConfig {
+    fn default() -> Self {
+        Self {
+            finger: FingerType::Index,
+            min_radius: 5.0,
+            min_arc: 1.5 * PI,
+            min_speed: 100.0,
+            window: Duration::from_millis(1500),
+        }
+    }
+}
+
+/// Data of a circle gesture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Vector,
+    /// The normal of the plane the circle lies in.
+    pub normal: Vector,
+    pub radius: f32,
+    /// The number of revolutions traced so far.
+    pub progress: f32,
+    /// Whether the circle is traced clockwise, as seen from the finger's point of view.
+    pub clockwise: bool,
+}
+
+/// The circle best matching a series of points.
+struct Fit {
+    center: Vector,
+    normal: Vector,
+    radius: f32,
+}
+
+impl Fit {
+    fn new(points: &VecDeque<(Timestamp, Vector)>) -> Self {
+        let center = points.iter().fold(Vector::ZERO, |sum, &(_, p)| sum + p) / points.len() as f32;
+        let normal = points
+            .iter()
+            .zip(points.iter().skip(1))
+            .fold(Vector::ZERO, |sum, (&(_, a), &(_, b))| {
+                sum + (a - center).cross(b - center)
+            })
+            .normalized();
+        let radius = points
+            .iter()
+            .map(|&(_, p)| p.distance_to(center))
+            .sum::<f32>()
+            / points.len() as f32;
+        Self {
+            center,
+            normal,
+            radius,
+        }
+    }
+
+    /// Returns the signed angle swept from `a` to `b`, around the fitted normal.
+    fn swept_angle(&self, a: Vector, b: Vector) -> f32 {
+        let (a, b) = (a - self.center, b - self.center);
+        let angle = a.angle_to(b);
+        if a.cross(b).dot(self.normal) < 0.0 {
+            -angle
+        } else {
+            angle
+        }
+    }
+}
+
+/// Recognizes a finger tip moving in a circle.
+#[derive(Debug)]
+pub struct CircleRecognizer {
+    config: CircleConfig,
+    points: VecDeque<(Timestamp, Vector)>,
+    /// Revolutions traced since the circle started.
+    progress: f32,
+    tracker: Tracker<Circle>,
+}
+
+impl CircleRecognizer {
+    pub fn new(config: CircleConfig) -> Self {
+        Self {
+            config,
+            points: VecDeque::new(),
+            progress: 0.0,
+            tracker: Tracker::new(),
+        }
+    }
+}
+
+impl Default for CircleRecognizer {
+    fn default() -> Self {
+        Self::new(CircleConfig::default())
+    }
+}
+
+impl Recognizer for CircleRecognizer {
+    type Event = Event<Circle>;
+
+    fn update(&mut self, hand: &HandSnapshot) -> Option<Self::Event> {
+        if self.tracker.is_other_hand(hand) {
+            if let Some(event) = self.reset() {
+                return Some(event);
+            }
+        }
+        self.tracker.track(hand);
+
+        let finger = hand.finger(self.config.finger);
+        if finger.tip_velocity.magnitude() < self.config.min_speed {
+            return self.reset();
+        }
+
+        let previous = self.points.back().map(|&(_, p)| p);
+        self.points.push_back((hand.timestamp, finger.tip_position));
+        let window = self.config.window.as_secs_f32();
+        while let Some(&(t, _)) = self.points.front() {
+            if seconds_between(t, hand.timestamp) <= window {
+                break;
+            }
+            self.points.pop_front();
+        }
+        if self.points.len() < 3 {
+            return None;
+        }
+
+        let fit = Fit::new(&self.points);
+        if fit.radius < self.config.min_radius {
+            return self.reset();
+        }
+
+        if self.tracker.is_active() {
+            if let Some(previous) = previous {
+                self.progress += fit.swept_angle(previous, finger.tip_position).abs() / TAU;
+            }
+        } else {
+            let arc: f32 = self
+                .points
+                .iter()
+                .zip(self.points.iter().skip(1))
+                .map(|(&(_, a), &(_, b))| fit.swept_angle(a, b))
+                .sum();
+            if arc.abs() < self.config.min_arc {
+                return None;
+            }
+            self.progress = arc.abs() / TAU;
+        }
+
+        let data = Circle {
+            center: fit.center,
+            normal: fit.normal,
+            radius: fit.radius,
+            progress: self.progress,
+            clockwise: finger.direction.angle_to(fit.normal) <= PI / 2.0,
+        };
+        Some(self.tracker.emit(hand, data))
+    }
+
+    fn reset(&mut self) -> Option<Self::Event> {
+        self.points.clear();
+        self.progress = 0.0;
+        self.tracker.reset()
+    }
+}
+
+/// Configuration of a [`TapRecognizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TapConfig {
+    /// The tapping finger.
+    pub finger: FingerType,
+    /// The direction of the tapping motion.
+    ///
+    /// Defaults to [`Vector::DOWN`], like a key press. Use [`Vector::FORWARD`] for taps on a
+    /// vertical screen.
+    pub direction: Vector,
+    /// The minimum finger tip speed along `direction`, in millimeters per second.
+    pub min_speed: f32,
+    /// The minimum distance the finger tip has to travel along `direction`, in millimeters.
+    pub min_distance: f32,
+    /// The maximum duration of the tapping motion.
+    pub max_duration: Duration,
+}
+
+impl Default for TapConfig {
+    fn default() -> Self {
+        Self {
+            finger: FingerType::Index,
+            direction: Vector::DOWN,
+            min_speed: 50.0,
+            min_distance: 3.0,
+            max_duration: Duration::from_millis(200),
+        }
+    }
+}
+
+/// Data of a tap gesture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tap {
+    /// The finger tip position at which the motion reversed.
+    pub position: Vector,
+    /// The distance travelled along the tapping direction, in millimeters.
+    pub distance: f32,
+}
+
+/// Recognizes a quick tapping motion of a finger.
+///
+/// Taps are discrete: every tap is reported by a single event in the
+/// [`GestureState::Stop`] state, once the finger stops or reverses.
+#[derive(Debug)]
+pub struct TapRecognizer {
+    config: TapConfig,
+    hand_id: Option<i32>,
+    /// The time and finger tip position at which the tapping motion started.
+    press: Option<(Timestamp, Vector)>,
+}
+
+impl TapRecognizer {
+    pub fn new(config: TapConfig) -> Self {
+        Self {
+            config,
+            hand_id: None,
+            press: None,
+        }
+    }
+}
+
+impl Default for TapRecognizer {
+    fn default() -> Self {
+        Self::new(TapConfig::default())
+    }
+}
+
+impl Recognizer for TapRecognizer {
+    type Event = Event<Tap>;
+
+    fn update(&mut self, hand: &HandSnapshot) -> Option<Self::Event> {
+        if self.hand_id != Some(hand.id) {
+            self.reset();
+            self.hand_id = Some(hand.id);
+        }
+
+        let direction = self.config.direction.normalized();
+        let finger = hand.finger(self.config.finger);
+        let speed = finger.tip_velocity.dot(direction);
+
+        let (start, start_position) = match self.press {
+            Some(press) => press,
+            None => {
+                if speed >= self.config.min_speed {
+                    self.press = Some((hand.timestamp, finger.tip_position));
+                }
+                return None;
+            }
+        };
+
+        let elapsed = seconds_between(start, hand.timestamp);
+        if elapsed > self.config.max_duration.as_secs_f32() {
+            self.press = None;
+            return None;
+        }
+        if speed > 0.0 {
+            return None;
+        }
+
+        self.press = None;
+        let distance = (finger.tip_position - start_position).dot(direction);
+        if distance < self.config.min_distance {
+            return None;
+        }
+        Some(Event {
+            state: GestureState::Stop,
+            hand_id: hand.id,
+            timestamp: hand.timestamp,
+            data: Tap {
+                position: finger.tip_position,
+                distance,
+            },
+        })
+    }
+
+    fn reset(&mut self) -> Option<Self::Event> {
+        self.hand_id = None;
+        self.press = None;
+        None
+    }
+}
+
+/// Configuration of a [`ThumbsUpRecognizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThumbsUpConfig {
+    /// The maximum angle between the thumb and [`Vector::UP`], in radians.
+    pub max_angle: f32,
+    /// How long the pose has to be held before the gesture starts.
+    pub hold_time: Duration,
+}
+
+impl Default for ThumbsUpConfig {
+    fn default() -> Self {
+        Self {
+            max_angle: FRAC_PI_6,
+            hold_time: Duration::from_millis(250),
+        }
+    }
+}
+
+/// Data of a thumbs-up gesture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThumbsUp {
+    /// The direction the thumb is pointing in.
+    pub direction: Vector,
+}
+
+/// Recognizes a fist with the thumb extended and pointing up.
+#[derive(Debug)]
+pub struct ThumbsUpRecognizer {
+    config: ThumbsUpConfig,
+    /// The time at which the pose was first observed.
+    since: Option<Timestamp>,
+    tracker: Tracker<ThumbsUp>,
+}
+
+impl ThumbsUpRecognizer {
+    pub fn new(config: ThumbsUpConfig) -> Self {
+        Self {
+            config,
+            since: None,
+            tracker: Tracker::new(),
+        }
+    }
+}
+
+impl Default for ThumbsUpRecognizer {
+    fn default() -> Self {
+        Self::new(ThumbsUpConfig::default())
+    }
+}
+
+impl Recognizer for ThumbsUpRecognizer {
+    type Event = Event<ThumbsUp>;
+
+    fn update(&mut self, hand: &HandSnapshot) -> Option<Self::Event> {
+        if self.tracker.is_other_hand(hand) {
+            if let Some(event) = self.reset() {
+                return Some(event);
+            }
+        }
+        self.tracker.track(hand);
+
+        let thumb = hand.finger(FingerType::Thumb);
+        let is_pose = thumb.is_extended
+            && hand.fingers[1..].iter().all(|finger| !finger.is_extended)
+            && thumb.direction.angle_to(Vector::UP) <= self.config.max_angle;
+        if !is_pose {
+            return self.reset();
+        }
+
+        let since = *self.since.get_or_insert(hand.timestamp);
+        if seconds_between(since, hand.timestamp) < self.config.hold_time.as_secs_f32() {
+            return None;
+        }
+
+        let data = ThumbsUp {
+            direction: thumb.direction,
+        };
+        Some(self.tracker.emit(hand, data))
+    }
+
+    fn reset(&mut self) -> Option<Self::Event> {
+        self.since = None;
+        self.tracker.reset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::fixtures;
+
+    fn hand(id: i32, millis: i64) -> HandSnapshot {
+        HandSnapshot {
+            timestamp: Timestamp::from_raw(millis * 1000),
+            id,
+            is_left: false,
+            palm_position: Vector::ZERO,
+            palm_velocity: Vector::ZERO,
+            palm_normal: Vector::DOWN,
+            direction: Vector::FORWARD,
+            pinch_strength: 0.0,
+            grab_strength: 0.0,
+            fingers: [FingerSnapshot::default(); 5],
+        }
+    }
+
+    fn pinching(id: i32, millis: i64, strength: f32) -> HandSnapshot {
+        HandSnapshot {
+            pinch_strength: strength,
+            ..hand(id, millis)
+        }
+    }
+
+    /// A hand moving to the right at 2 m/s, observed every 10 ms.
+    fn swiping(id: i32, millis: i64, x: f32) -> HandSnapshot {
+        HandSnapshot {
+            palm_position: Vector::new(x, 200.0, 0.0),
+            palm_velocity: Vector::new(2000.0, 0.0, 0.0),
+            ..hand(id, millis)
+        }
+    }
+
+    fn thumbs_up(id: i32, millis: i64) -> HandSnapshot {
+        let mut hand = hand(id, millis);
+        hand.fingers[FingerType::Thumb as usize] = FingerSnapshot {
+            direction: Vector::UP,
+            is_extended: true,
+            ..FingerSnapshot::default()
+        };
+        hand
+    }
+
+    fn states<T>(events: &[Option<Event<T>>]) -> Vec<Option<GestureState>> {
+        events
+            .iter()
+            .map(|event| event.as_ref().map(|event| event.state))
+            .collect()
+    }
+
+    #[test]
+    fn snapshot_from_frame_data() {
+        let mut hand = fixtures::hand(3, true, Vector::new(0.0, 200.0, 0.0));
+        hand.fingers.swap(0, 4);
+        hand.fingers.pop();
+        let frame = fixtures::frame(1, vec![hand]);
+
+        let snapshots = HandSnapshot::from_frame_data(&frame);
+        assert_eq!(snapshots.len(), 1);
+        let snapshot = &snapshots[0];
+        assert_eq!(snapshot.timestamp, frame.timestamp);
+        assert_eq!((snapshot.id, snapshot.is_left), (3, true));
+        // Fingers are indexed by type, regardless of their order, and missing ones stay default.
+        let pinky = frame.hands[0].finger(FingerType::Pinky).unwrap();
+        assert_eq!(
+            snapshot.finger(FingerType::Pinky).tip_position,
+            pinky.pointable.tip_position,
+        );
+        assert_eq!(
+            *snapshot.finger(FingerType::Ring),
+            FingerSnapshot::from_finger_data(frame.hands[0].finger(FingerType::Ring).unwrap()),
+        );
+        assert_eq!(
+            *snapshot.finger(FingerType::Thumb),
+            FingerSnapshot::default()
+        );
+    }
+
+    #[test]
+    fn pinch_hysteresis() {
+        let mut recognizer = PinchRecognizer::default();
+        let events = [0.5, 0.85, 0.7, 0.5, 0.7]
+            .iter()
+            .enumerate()
+            .map(|(i, &strength)| recognizer.update(&pinching(1, i as i64 * 10, strength)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            states(&events),
+            [
+                None,
+                Some(GestureState::Start),
+                Some(GestureState::Update),
+                Some(GestureState::Stop),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn pinch_stops_when_hand_changes() {
+        let mut recognizer = PinchRecognizer::default();
+        assert_eq!(
+            recognizer.update(&pinching(1, 0, 0.9)).unwrap().state,
+            GestureState::Start
+        );
+
+        let stop = recognizer.update(&pinching(2, 10, 0.9)).unwrap();
+        assert_eq!(stop.state, GestureState::Stop);
+        assert_eq!(stop.hand_id, 1);
+
+        let start = recognizer.update(&pinching(2, 20, 0.9)).unwrap();
+        assert_eq!(start.state, GestureState::Start);
+        assert_eq!(start.hand_id, 2);
+    }
+
+    #[test]
+    fn grab_reset_stops_gesture() {
+        let mut recognizer = GrabRecognizer::default();
+        let fist = HandSnapshot {
+            grab_strength: 1.0,
+            ..hand(1, 0)
+        };
+        assert!(recognizer.update(&fist).is_some());
+        assert_eq!(recognizer.reset().unwrap().state, GestureState::Stop);
+        assert_eq!(recognizer.reset(), None);
+    }
+
+    #[test]
+    fn swipe() {
+        let mut recognizer = SwipeRecognizer::default();
+        let mut events = (0..10)
+            .map(|i| recognizer.update(&swiping(1, i * 10, i as f32 * 20.0)))
+            .collect::<Vec<_>>();
+        events.push(recognizer.update(&hand(1, 100)));
+
+        // The swipe starts once the palm travelled 150 mm.
+        assert!(events[..8].iter().all(Option::is_none));
+        let start = events[8].unwrap();
+        assert_eq!(start.state, GestureState::Start);
+        assert_eq!(start.data.start_position, Vector::new(0.0, 200.0, 0.0));
+        assert_eq!(start.data.main_direction(), SwipeDirection::Right);
+        assert_eq!(events[9].unwrap().state, GestureState::Update);
+        assert_eq!(events[10].unwrap().state, GestureState::Stop);
+    }
+
+    #[test]
+    fn swipe_does_not_start_from_other_hand() {
+        let mut recognizer = SwipeRecognizer::default();
+        for i in 0..5 {
+            assert_eq!(
+                recognizer.update(&swiping(1, i * 10, i as f32 * 20.0)),
+                None
+            );
+        }
+
+        // Far enough from where the first hand started, but the second hand only just appeared.
+        assert_eq!(recognizer.update(&swiping(2, 50, 300.0)), None);
+        for i in 1..8 {
+            let event = recognizer.update(&swiping(2, 50 + i * 10, 300.0 + i as f32 * 20.0));
+            assert_eq!(event, None);
+        }
+        let start = recognizer.update(&swiping(2, 130, 460.0)).unwrap();
+        assert_eq!(start.hand_id, 2);
+        assert_eq!(start.data.start_position, Vector::new(300.0, 200.0, 0.0));
+    }
+
+    #[test]
+    fn circle() {
+        const RADIUS: f32 = 50.0;
+        const STEPS_PER_REVOLUTION: usize = 36;
+        let center = Vector::new(0.0, 200.0, 0.0);
+        let angular_speed = TAU / (STEPS_PER_REVOLUTION as f32 * 0.02);
+
+        let mut recognizer = CircleRecognizer::default();
+        let mut events = Vec::new();
+        for i in 0..STEPS_PER_REVOLUTION * 2 {
+            let angle = i as f32 * TAU / STEPS_PER_REVOLUTION as f32;
+            let (sin, cos) = angle.sin_cos();
+            let mut hand = hand(1, i as i64 * 20);
+            hand.fingers[FingerType::Index as usize] = FingerSnapshot {
+                tip_position: center + Vector::new(cos, sin, 0.0) * RADIUS,
+                tip_velocity: Vector::new(-sin, cos, 0.0) * RADIUS * angular_speed,
+                direction: Vector::FORWARD,
+                is_extended: true,
+            };
+            events.push(recognizer.update(&hand));
+        }
+
+        let start = events.iter().position(Option::is_some).unwrap();
+        // The circle is fitted through the centroid of the points, which overestimates the arc of
+        // an incomplete circle, so it starts somewhat before `min_arc` is traced.
+        assert!(start > STEPS_PER_REVOLUTION / 2 && start < STEPS_PER_REVOLUTION);
+        assert_eq!(events[start].unwrap().state, GestureState::Start);
+        assert!(events[start + 1..].iter().all(|event| matches!(
+            event,
+            Some(Event {
+                state: GestureState::Update,
+                ..
+            })
+        )));
+
+        let last = events.last().unwrap().unwrap().data;
+        assert!(last.center.distance_to(center) < 5.0, "{:?}", last);
+        assert!((last.radius - RADIUS).abs() < 5.0, "{:?}", last);
+        assert!(last.normal.angle_to(Vector::Z_AXIS) < 0.1, "{:?}", last);
+        assert!(last.progress > 1.5, "{:?}", last);
+    }
+
+    #[test]
+    fn tap() {
+        let finger = |millis, y, speed| {
+            let mut hand = hand(1, millis);
+            hand.fingers[FingerType::Index as usize] = FingerSnapshot {
+                tip_position: Vector::new(0.0, y, 0.0),
+                tip_velocity: Vector::new(0.0, speed, 0.0),
+                ..FingerSnapshot::default()
+            };
+            hand
+        };
+
+        let mut recognizer = TapRecognizer::default();
+        assert_eq!(recognizer.update(&finger(0, 200.0, -200.0)), None);
+        assert_eq!(recognizer.update(&finger(20, 196.0, -200.0)), None);
+        let tap = recognizer.update(&finger(40, 192.0, 10.0)).unwrap();
+        assert_eq!(tap.state, GestureState::Stop);
+        assert_eq!(tap.data.distance, 8.0);
+        assert_eq!(tap.data.position, Vector::new(0.0, 192.0, 0.0));
+
+        // Too slow: the motion takes longer than `max_duration`.
+        assert_eq!(recognizer.update(&finger(100, 200.0, -200.0)), None);
+        assert_eq!(recognizer.update(&finger(400, 150.0, -200.0)), None);
+        assert_eq!(recognizer.update(&finger(420, 150.0, 10.0)), None);
+    }
+
+    #[test]
+    fn thumbs_up_after_hold_time() {
+        let mut recognizer = ThumbsUpRecognizer::default();
+        assert_eq!(recognizer.update(&thumbs_up(1, 0)), None);
+        assert_eq!(recognizer.update(&thumbs_up(1, 100)), None);
+        let start = recognizer.update(&thumbs_up(1, 300)).unwrap();
+        assert_eq!(start.state, GestureState::Start);
+        assert_eq!(start.data.direction, Vector::UP);
+
+        assert_eq!(
+            recognizer.update(&hand(1, 310)).unwrap().state,
+            GestureState::Stop
+        );
+    }
+
+    #[test]
+    fn thumbs_up_hold_time_is_per_hand() {
+        let mut recognizer = ThumbsUpRecognizer::default();
+        assert_eq!(recognizer.update(&thumbs_up(1, 0)), None);
+        assert_eq!(recognizer.update(&thumbs_up(1, 200)), None);
+
+        // The pose was held for long enough, but not by the same hand.
+        assert_eq!(recognizer.update(&thumbs_up(2, 260)), None);
+        assert_eq!(recognizer.update(&thumbs_up(2, 400)), None);
+        assert_eq!(recognizer.update(&thumbs_up(2, 520)).unwrap().hand_id, 2);
+    }
+}
@@ -0,0 +1,683 @@
+//! Recording and replaying of tracking sessions.
+//!
+//! A [`Recorder`] is a [`Listener`] that writes everything reported by a [`Controller`] to a file:
+//! tracking frames, camera images (if [`Policy::Images`][crate::Policy::Images] is set), and
+//! connection, focus, and device change events. The recording can later be read back with a
+//! [`Reader`], and fed to any [`Listener`] via [`replay`], without a Leap Motion device.
+//!
+//! # File format
+//!
+//! A recording starts with the 8-byte magic `LEAPREC\0`, followed by the format version as a
+//! little-endian `u32`. The rest of the file is a sequence of chunks, each consisting of a 4-byte
+//! tag, the length of the payload as a little-endian `u32`, and the payload itself. Every payload
+//! starts with the time at which it was recorded, as a little-endian `i64` in microseconds.
+//!
+//! Chunks with unknown tags are skipped, so new kinds of chunks can be added without breaking
+//! existing readers. Incompatible changes to existing chunks increment the version.
+
+use std::{
+    io::{self, Read, Write},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    backend::{VirtualInner, VirtualState},
+    image::{Camera, ImageBuf},
+    Controller, ControllerRef, Frame, Listener, Timestamp,
+};
+
+const MAGIC: &[u8; 8] = b"LEAPREC\0";
+
+/// The version of the file format written by [`Recorder`].
+pub const VERSION: u32 = 1;
+
+const TAG_STATUS: [u8; 4] = *b"STAT";
+const TAG_EVENT: [u8; 4] = *b"EVNT";
+const TAG_FRAME: [u8; 4] = *b"FRAM";
+const TAG_IMAGES: [u8; 4] = *b"IMGS";
+
+/// An entry in a recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// The time at which the entry was recorded, as reported by [`ControllerRef::now`].
+    pub timestamp: Timestamp,
+    pub payload: Payload,
+}
+
+/// The data recorded in a [`Record`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Payload {
+    /// The state of the controller when the recording was started.
+    Status(Status),
+    /// A [`Listener`] event without associated data.
+    Event(Event),
+    /// A frame of tracking data, serialized with [`Frame::serialize`][crate::Frame::serialize].
+    Frame(Vec<u8>),
+    /// A set of camera images.
+    Images(Vec<ImageBuf>),
+}
+
+/// The connection state of a controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Status {
+    pub service_connected: bool,
+    pub connected: bool,
+    pub has_focus: bool,
+}
+
+impl Status {
+    fn from_bits(bits: u8) -> Self {
+        Self {
+            service_connected: bits & 1 != 0,
+            connected: bits & 2 != 0,
+            has_focus: bits & 4 != 0,
+        }
+    }
+
+    fn to_bits(self) -> u8 {
+        self.service_connected as u8 | (self.connected as u8) << 1 | (self.has_focus as u8) << 2
+    }
+
+    fn apply(self, state: &mut VirtualInner) {
+        state.service_connected = self.service_connected;
+        state.connected = self.connected;
+        state.has_focus = self.has_focus;
+    }
+}
+
+/// A [`Listener`] event that carries no data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Event {
+    Connect = 0,
+    Disconnect = 1,
+    FocusGained = 2,
+    FocusLost = 3,
+    ServiceConnect = 4,
+    ServiceDisconnect = 5,
+    DeviceChange = 6,
+}
+
+impl Event {
+    fn from_raw(raw: u8) -> io::Result<Self> {
+        Ok(match raw {
+            0 => Self::Connect,
+            1 => Self::Disconnect,
+            2 => Self::FocusGained,
+            3 => Self::FocusLost,
+            4 => Self::ServiceConnect,
+            5 => Self::ServiceDisconnect,
+            6 => Self::DeviceChange,
+            _ => return Err(invalid_data(format!("invalid event {}", raw))),
+        })
+    }
+
+    /// Updates the controller state to reflect this event having happened.
+    pub(crate) fn apply(self, state: &mut VirtualInner) {
+        match self {
+            Self::Connect => state.connected = true,
+            Self::Disconnect => state.connected = false,
+            Self::FocusGained => state.has_focus = true,
+            Self::FocusLost => state.has_focus = false,
+            Self::ServiceConnect => state.service_connected = true,
+            Self::ServiceDisconnect => state.service_connected = false,
+            Self::DeviceChange => {}
+        }
+    }
+
+    pub(crate) fn dispatch<L: Listener + ?Sized>(
+        self,
+        listener: &mut L,
+        controller: &ControllerRef,
+    ) {
+        match self {
+            Self::Connect => listener.on_connect(controller),
+            Self::Disconnect => listener.on_disconnect(controller),
+            Self::FocusGained => listener.on_focus_gained(controller),
+            Self::FocusLost => listener.on_focus_lost(controller),
+            Self::ServiceConnect => listener.on_service_connect(controller),
+            Self::ServiceDisconnect => listener.on_service_disconnect(controller),
+            Self::DeviceChange => listener.on_device_change(controller),
+        }
+    }
+}
+
+/// Writes a recording.
+///
+/// [`Recorder`] implements [`Listener`], so the simplest way to record a session is to add it to a
+/// [`Controller`]. Since listeners cannot report errors, the recorder logs the first write error
+/// and stops recording. Alternatively, [`Record`]s can be written manually with
+/// [`Recorder::write`].
+pub struct Recorder<W: Write> {
+    writer: W,
+    /// Set after a write error, to stop recording.
+    failed: bool,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Creates a recorder writing to `writer`, and writes the file header.
+    ///
+    /// `writer` should be buffered, eg. using [`BufWriter`][std::io::BufWriter].
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        Ok(Self {
+            writer,
+            failed: false,
+        })
+    }
+
+    /// Appends a record to the recording.
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        let mut payload = record.timestamp.as_raw().to_le_bytes().to_vec();
+        let tag = match &record.payload {
+            Payload::Status(status) => {
+                payload.push(status.to_bits());
+                TAG_STATUS
+            }
+            Payload::Event(event) => {
+                payload.push(*event as u8);
+                TAG_EVENT
+            }
+            Payload::Frame(data) => {
+                payload.extend_from_slice(data);
+                TAG_FRAME
+            }
+            Payload::Images(images) => {
+                put_len(&mut payload, images.len())?;
+                for image in images {
+                    encode_image(&mut payload, image)?;
+                }
+                TAG_IMAGES
+            }
+        };
+
+        self.writer.write_all(&tag)?;
+        put_len(&mut self.writer, payload.len())?;
+        self.writer.write_all(&payload)
+    }
+
+    /// Flushes the recording and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn record(&mut self, controller: &ControllerRef, payload: Payload) {
+        if self.failed {
+            return;
+        }
+
+        let record = Record {
+            timestamp: controller.now(),
+            payload,
+        };
+        if let Err(e) = self.write(&record) {
+            log::error!("failed to write recording, stopping: {}", e);
+            self.failed = true;
+        }
+    }
+}
+
+impl<W: Write + Send + 'static> Listener for Recorder<W> {
+    fn on_init(&mut self, controller: &ControllerRef) {
+        let status = Status {
+            service_connected: controller.is_service_connected(),
+            connected: controller.is_connected(),
+            has_focus: controller.has_focus(),
+        };
+        self.record(controller, Payload::Status(status));
+    }
+
+    fn on_connect(&mut self, controller: &ControllerRef) {
+        self.record(controller, Payload::Event(Event::Connect));
+    }
+
+    fn on_disconnect(&mut self, controller: &ControllerRef) {
+        self.record(controller, Payload::Event(Event::Disconnect));
+    }
+
+    fn on_exit(&mut self, _: &ControllerRef) {
+        if let Err(e) = self.writer.flush() {
+            log::error!("failed to flush recording: {}", e);
+        }
+    }
+
+    fn on_frame(&mut self, controller: &ControllerRef) {
+        if let Some(frame) = controller.frame() {
+            self.record(controller, Payload::Frame(frame.serialize()));
+        }
+    }
+
+    fn on_focus_gained(&mut self, controller: &ControllerRef) {
+        self.record(controller, Payload::Event(Event::FocusGained));
+    }
+
+    fn on_focus_lost(&mut self, controller: &ControllerRef) {
+        self.record(controller, Payload::Event(Event::FocusLost));
+    }
+
+    fn on_service_connect(&mut self, controller: &ControllerRef) {
+        self.record(controller, Payload::Event(Event::ServiceConnect));
+    }
+
+    fn on_service_disconnect(&mut self, controller: &ControllerRef) {
+        self.record(controller, Payload::Event(Event::ServiceDisconnect));
+    }
+
+    fn on_device_change(&mut self, controller: &ControllerRef) {
+        self.record(controller, Payload::Event(Event::DeviceChange));
+    }
+
+    fn on_images(&mut self, controller: &ControllerRef) {
+        let images = controller
+            .images()
+            .iter()
+            .map(|image| image.to_buf())
+            .collect();
+        match images {
+            Ok(images) => self.record(controller, Payload::Images(images)),
+            Err(e) => log::warn!("not recording images: {}", e),
+        }
+    }
+}
+
+/// Reads a recording.
+///
+/// The [`Record`]s are read lazily, by iterating over the [`Reader`]. Iteration ends after the
+/// first error.
+pub struct Reader<R: Read> {
+    reader: R,
+    version: u32,
+    failed: bool,
+}
+
+impl<R: Read> Reader<R> {
+    /// Creates a reader for the recording in `reader`, and reads the file header.
+    ///
+    /// Returns an error if `reader` does not contain a recording in a supported version.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a recording".to_string()));
+        }
+
+        let mut version = [0; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version == 0 || version > VERSION {
+            return Err(invalid_data(format!(
+                "unsupported recording version {}",
+                version
+            )));
+        }
+
+        Ok(Self {
+            reader,
+            version,
+            failed: false,
+        })
+    }
+
+    /// Returns the format version of the recording.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<Record>> {
+        loop {
+            let tag = match self.read_tag()? {
+                Some(tag) => tag,
+                None => return Ok(None),
+            };
+
+            let mut len = [0; 4];
+            self.reader.read_exact(&mut len)?;
+            let len = u32::from_le_bytes(len);
+
+            // Don't trust the length enough to allocate it upfront, the file might be corrupt.
+            let mut payload = Vec::new();
+            (&mut self.reader)
+                .take(len.into())
+                .read_to_end(&mut payload)?;
+            if payload.len() != len as usize {
+                return Err(invalid_data("truncated chunk".to_string()));
+            }
+
+            if let Some(record) = decode_record(tag, &payload)? {
+                return Ok(Some(record));
+            }
+        }
+    }
+
+    /// Reads the tag of the next chunk, or returns `None` at the end of the recording.
+    fn read_tag(&mut self) -> io::Result<Option<[u8; 4]>> {
+        let mut tag = [0; 4];
+        let mut filled = 0;
+        while filled < tag.len() {
+            match self.reader.read(&mut tag[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(Some(tag))
+    }
+}
+
+impl<R: Read> Iterator for Reader<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let result = self.read_record().transpose();
+        if let Some(Err(_)) = result {
+            self.failed = true;
+        }
+        result
+    }
+}
+
+/// The speed at which [`replay`] plays back a recording.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Speed {
+    /// Plays back the records with the timing they were recorded with.
+    Original,
+    /// Plays back the records faster (factor > 1.0) or slower (factor < 1.0) than recorded.
+    Scaled(f32),
+    /// Plays back the records as fast as possible.
+    Unlimited,
+}
+
+/// Plays back a recording through `listener`.
+///
+/// The listener is invoked on the calling thread, with a [`ControllerRef`] that reflects the
+/// recorded state: [`ControllerRef::frame`] returns the recorded frames, [`ControllerRef::images`]
+/// the recorded images, and [`ControllerRef::now`] the time at which the current record was
+/// recorded. [`Listener::on_init`] and [`Listener::on_exit`] are invoked before and after the
+/// recording is played back.
+///
+/// The recorded frames are decoded in order as they are played back. Since the SDK can only decode
+/// frames while a [`Controller`] exists, one is created for the duration of the replay; it does
+/// not need to be connected to leapd.
+///
+/// Returns once all records have been played back, or after the first error. An error of kind
+/// [`InvalidInput`][io::ErrorKind::InvalidInput] is returned without playing anything back if
+/// `speed` is [`Speed::Scaled`] with a factor that is not positive.
+pub fn replay<I, L>(records: I, speed: Speed, listener: &mut L) -> io::Result<()>
+where
+    I: IntoIterator<Item = io::Result<Record>>,
+    L: Listener + ?Sized,
+{
+    let factor = match speed {
+        Speed::Original => Some(1.0),
+        Speed::Scaled(factor) if factor > 0.0 => Some(factor as f64),
+        Speed::Scaled(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "replay speed must be positive",
+            ))
+        }
+        Speed::Unlimited => None,
+    };
+
+    let _sdk = Controller::new();
+    let state = Arc::new(VirtualState::default());
+    let controller = ControllerRef::from_virtual(state.clone());
+
+    listener.on_init(&controller);
+    let result = play(records, factor, &state, &controller, listener);
+    listener.on_exit(&controller);
+    result
+}
+
+fn play<I, L>(
+    records: I,
+    factor: Option<f64>,
+    state: &VirtualState,
+    controller: &ControllerRef,
+    listener: &mut L,
+) -> io::Result<()>
+where
+    I: IntoIterator<Item = io::Result<Record>>,
+    L: Listener + ?Sized,
+{
+    let mut start: Option<(Instant, Timestamp)> = None;
+    for record in records {
+        let record = record?;
+
+        if let Some(factor) = factor {
+            let (start_instant, start_timestamp) =
+                *start.get_or_insert((Instant::now(), record.timestamp));
+            let elapsed = (record.timestamp.as_raw() - start_timestamp.as_raw()).max(0);
+            let target = start_instant + Duration::from_secs_f64(elapsed as f64 / 1e6 / factor);
+            thread::sleep(target.saturating_duration_since(Instant::now()));
+        }
+
+        state.lock().now = record.timestamp.as_raw();
+        match record.payload {
+            Payload::Status(status) => status.apply(&mut state.lock()),
+            Payload::Event(event) => {
+                event.apply(&mut state.lock());
+                event.dispatch(listener, controller);
+            }
+            Payload::Frame(data) => {
+                let frame = Frame::deserialize(&data);
+                if let Err(e) = &frame {
+                    log::warn!("failed to decode recorded frame: {}", e);
+                }
+                state.lock().push_frame(frame.ok());
+                listener.on_frame(controller);
+            }
+            Payload::Images(images) => {
+                state.lock().images = images.into_iter().map(Arc::new).collect();
+                listener.on_images(controller);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn encode_image(out: &mut Vec<u8>, image: &ImageBuf) -> io::Result<()> {
+    out.extend_from_slice(&image.sequence_id().to_le_bytes());
+    out.push(match image.camera() {
+        Camera::Left => 0,
+        Camera::Right => 1,
+    });
+    out.extend_from_slice(&image.timestamp().as_raw().to_le_bytes());
+    put_len(out, image.width())?;
+    put_len(out, image.height())?;
+    put_len(out, image.bytes_per_pixel())?;
+    put_len(out, image.data().len())?;
+    out.extend_from_slice(image.data());
+    put_len(out, image.distortion().len())?;
+    for value in image.distortion() {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    Ok(())
+}
+
+fn decode_record(tag: [u8; 4], payload: &[u8]) -> io::Result<Option<Record>> {
+    let mut decoder = Decoder { data: payload };
+    let timestamp = Timestamp::from_raw(decoder.i64()?);
+    let payload = match tag {
+        TAG_STATUS => Payload::Status(Status::from_bits(decoder.u8()?)),
+        TAG_EVENT => Payload::Event(Event::from_raw(decoder.u8()?)?),
+        TAG_FRAME => Payload::Frame(decoder.data.to_vec()),
+        TAG_IMAGES => {
+            let count = decoder.len()?;
+            let images = (0..count)
+                .map(|_| decoder.image())
+                .collect::<io::Result<_>>()?;
+            Payload::Images(images)
+        }
+        _ => {
+            log::debug!("skipping unknown recording chunk {:?}", tag);
+            return Ok(None);
+        }
+    };
+
+    Ok(Some(Record { timestamp, payload }))
+}
+
+/// Reads values from a chunk payload.
+struct Decoder<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn take<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let bytes = self.bytes(N)?;
+        Ok(<[u8; N]>::try_from(bytes).unwrap())
+    }
+
+    fn bytes(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(invalid_data("truncated chunk".to_string()));
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn len(&mut self) -> io::Result<usize> {
+        Ok(u32::from_le_bytes(self.take()?) as usize)
+    }
+
+    fn i64(&mut self) -> io::Result<i64> {
+        Ok(i64::from_le_bytes(self.take()?))
+    }
+
+    fn f32(&mut self) -> io::Result<f32> {
+        Ok(f32::from_le_bytes(self.take()?))
+    }
+
+    fn image(&mut self) -> io::Result<ImageBuf> {
+        let sequence_id = self.i64()?;
+        let camera =
+            Camera::try_from(i32::from(self.u8()?)).map_err(|e| invalid_data(e.to_string()))?;
+        let timestamp = Timestamp::from_raw(self.i64()?);
+        let width = self.len()?;
+        let height = self.len()?;
+        let bytes_per_pixel = self.len()?;
+        let data_len = self.len()?;
+        let data = self.bytes(data_len)?.to_vec();
+        let distortion_len = self.len()?;
+        let distortion = (0..distortion_len)
+            .map(|_| self.f32())
+            .collect::<io::Result<_>>()?;
+
+        let image = ImageBuf::new(camera, width, height, bytes_per_pixel, data)
+            .and_then(|image| image.with_distortion(distortion))
+            .map_err(|e| invalid_data(e.to_string()))?;
+        Ok(image
+            .with_sequence_id(sequence_id)
+            .with_timestamp(timestamp))
+    }
+}
+
+/// Writes a length as a little-endian `u32`.
+fn put_len(out: &mut impl Write, len: usize) -> io::Result<()> {
+    let len = u32::try_from(len).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "recorded data exceeds 4 GiB limit",
+        )
+    })?;
+    out.write_all(&len.to_le_bytes())
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording(chunks: &[u8]) -> Vec<u8> {
+        let mut data = MAGIC.to_vec();
+        data.extend_from_slice(&VERSION.to_le_bytes());
+        data.extend_from_slice(chunks);
+        data
+    }
+
+    #[test]
+    fn truncated_chunk() {
+        let mut chunk = TAG_EVENT.to_vec();
+        chunk.extend_from_slice(&u32::MAX.to_le_bytes());
+        chunk.extend_from_slice(&[0; 9]);
+        let data = recording(&chunk);
+
+        let mut reader = Reader::new(&data[..]).unwrap();
+        let error = reader.next().unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn images_round_trip() {
+        let image = ImageBuf::new(Camera::Right, 4, 2, 1, (0..8).collect())
+            .unwrap()
+            .with_sequence_id(7)
+            .with_timestamp(Timestamp::from_raw(1234));
+        let record = Record {
+            timestamp: Timestamp::from_raw(1),
+            payload: Payload::Images(vec![image]),
+        };
+
+        let mut recorder = Recorder::new(Vec::new()).unwrap();
+        recorder.write(&record).unwrap();
+        let data = recorder.finish().unwrap();
+
+        let records = Reader::new(&data[..])
+            .unwrap()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(records, [record]);
+    }
+
+    #[test]
+    fn image_dimensions_mismatch() {
+        let mut payload = 0i64.to_le_bytes().to_vec();
+        put_len(&mut payload, 1).unwrap();
+        payload.extend_from_slice(&0i64.to_le_bytes());
+        payload.push(0);
+        payload.extend_from_slice(&0i64.to_le_bytes());
+        // Width 0, height 2, 1 byte per pixel, no data, no distortion map.
+        for len in [0, 2, 1, 0, 0] {
+            put_len(&mut payload, len).unwrap();
+        }
+        let mut chunk = TAG_IMAGES.to_vec();
+        put_len(&mut chunk, payload.len()).unwrap();
+        chunk.extend_from_slice(&payload);
+        let data = recording(&chunk);
+
+        let error = Reader::new(&data[..]).unwrap().next().unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn invalid_speed() {
+        struct Ignore;
+        impl Listener for Ignore {}
+
+        for factor in [0.0, -1.0, f32::NAN] {
+            let error = replay([], Speed::Scaled(factor), &mut Ignore).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use std::{fmt, time::Duration};
 
 /// A timestamp reported by the Leap Motion Service.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Timestamp(i64);
 
 impl Timestamp {
@@ -0,0 +1,585 @@
+//! Output of fingertips and hands using the [TUIO] protocol.
+//!
+//! [`Server`] is a [`Listener`] that publishes the tracked fingertips as TUIO cursors (pointers in
+//! TUIO 2.0) and the hands as blobs (bounds in TUIO 2.0), which is what multi-touch table software
+//! consumes. Positions are normalized through the interaction box and projected onto a [`Plane`].
+//!
+//! The TUIO messages are sent as OSC bundles over UDP, using the encoder of the [`osc`] module.
+//!
+//! [TUIO]: https://www.tuio.org/
+
+use std::{
+    collections::HashMap,
+    f32::consts::TAU,
+    io, mem,
+    net::{SocketAddr, ToSocketAddrs, UdpSocket},
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    data::{FrameData, InteractionBoxData, PointableData},
+    osc::{self, Bundle, Message, TimeTag},
+    pointable::{FingerType, TouchZone},
+    ControllerRef, Listener, Timestamp, Vector,
+};
+
+/// The default port of TUIO clients.
+pub const DEFAULT_PORT: u16 = 3333;
+
+/// The version of the TUIO protocol to send.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Version {
+    /// TUIO 1.1, using the `/tuio/2Dcur` and `/tuio/2Dblb` profiles.
+    Tuio11,
+    /// TUIO 2.0, using `/tuio2/ptr` and `/tuio2/bnd` components.
+    Tuio2,
+}
+
+/// The plane the 3D positions are projected onto.
+///
+/// In both cases, TUIO's X axis points right and its Y axis points towards the user or down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Plane {
+    /// The X-Y plane, as if the hands touched a screen facing the user.
+    Vertical,
+    /// The X-Z plane, as if the hands touched a table above the device.
+    Horizontal,
+}
+
+/// Which fingertips are published as cursors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorFilter {
+    /// All fingers of all hands.
+    All,
+    /// Only extended fingers.
+    Extended,
+    /// Only fingers in the [`TouchZone::Touching`] zone.
+    Touching,
+}
+
+/// The configuration of a [`Server`].
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub version: Version,
+    pub plane: Plane,
+    pub cursors: CursorFilter,
+    /// Whether to publish hands as blobs.
+    pub blobs: bool,
+    /// The source name sent to the clients.
+    pub source: String,
+    /// The sensor dimensions in pixels, sent in TUIO 2.0 frame messages.
+    pub dimensions: (u16, u16),
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            version: Version::Tuio11,
+            plane: Plane::Vertical,
+            cursors: CursorFilter::Extended,
+            blobs: true,
+            source: "leapcpp".into(),
+            dimensions: (1920, 1080),
+        }
+    }
+}
+
+/// A TUIO server sending the contents of every frame to a client over UDP.
+///
+/// Every fingertip and hand gets a new TUIO session ID when it starts being tracked, which it
+/// keeps until it is lost.
+pub struct Server {
+    socket: UdpSocket,
+    target: SocketAddr,
+    config: ServerConfig,
+    /// The TUIO frame sequence number.
+    frame_id: i32,
+    sessions: Sessions,
+    /// The timestamp of the first frame and the time it was received, to derive frame times.
+    epoch: Option<(Timestamp, SystemTime)>,
+}
+
+/// A tracked object that has a TUIO session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Component {
+    /// A fingertip, by pointable ID.
+    Cursor(i32),
+    /// A hand, by hand ID.
+    Blob(i32),
+}
+
+/// Assigns session IDs to the components of consecutive frames.
+#[derive(Debug, Default)]
+struct Sessions {
+    /// The last session ID assigned.
+    last: i32,
+    previous: HashMap<Component, i32>,
+    current: HashMap<Component, i32>,
+}
+
+impl Sessions {
+    /// Returns the session ID of a component of the current frame.
+    fn id(&mut self, component: Component) -> i32 {
+        let id = match self.previous.get(&component) {
+            Some(id) => *id,
+            None => {
+                self.last = self.last.wrapping_add(1);
+                self.last
+            }
+        };
+        self.current.insert(component, id);
+        id
+    }
+
+    /// Forgets the components that were not part of the current frame.
+    fn end_frame(&mut self) {
+        self.previous = mem::take(&mut self.current);
+    }
+}
+
+/// A cursor or blob, in TUIO coordinates.
+struct Shape {
+    session: i32,
+    /// The TUIO 2.0 type ID of a pointer. Bounds have no type.
+    type_id: Option<i32>,
+    position: (f32, f32),
+    velocity: (f32, f32),
+    angle: f32,
+    size: (f32, f32),
+    /// The TUIO 2.0 pressure: 0 to 1 while touching, negative while hovering.
+    pressure: f32,
+}
+
+impl Server {
+    /// Creates a server sending to `target`, usually `("127.0.0.1", DEFAULT_PORT)`.
+    pub fn new(target: impl ToSocketAddrs, config: ServerConfig) -> io::Result<Self> {
+        let (socket, target) = osc::udp_socket(target)?;
+        Ok(Self {
+            socket,
+            target,
+            config,
+            frame_id: 0,
+            sessions: Sessions::default(),
+            epoch: None,
+        })
+    }
+
+    /// Returns the OSC bundles describing `frame`, and advances the frame sequence number.
+    ///
+    /// Frames with an [empty](InteractionBoxData::is_empty) interaction box are skipped, since
+    /// their positions cannot be normalized.
+    pub fn bundles(&mut self, frame: &FrameData) -> Vec<Bundle> {
+        if frame.interaction_box.is_empty() {
+            log::debug!("skipping frame {} with empty interaction box", frame.id);
+            return Vec::new();
+        }
+
+        self.frame_id = self.frame_id.wrapping_add(1);
+        let (cursors, blobs) = self.shapes(frame);
+
+        match self.config.version {
+            Version::Tuio11 => {
+                let mut bundles = vec![self.bundle_11("/tuio/2Dcur", &cursors, |shape| {
+                    let (x, y) = shape.position;
+                    let (vx, vy) = shape.velocity;
+                    Message::new("/tuio/2Dcur")
+                        .arg("set")
+                        .arg(shape.session)
+                        .arg(x)
+                        .arg(y)
+                        .arg(vx)
+                        .arg(vy)
+                        .arg(0.0)
+                })];
+                if self.config.blobs {
+                    bundles.push(self.bundle_11("/tuio/2Dblb", &blobs, |shape| {
+                        let (x, y) = shape.position;
+                        let (vx, vy) = shape.velocity;
+                        let (w, h) = shape.size;
+                        Message::new("/tuio/2Dblb")
+                            .arg("set")
+                            .arg(shape.session)
+                            .arg(x)
+                            .arg(y)
+                            .arg(shape.angle)
+                            .arg(w)
+                            .arg(h)
+                            .arg(w * h)
+                            .arg(vx)
+                            .arg(vy)
+                            .arg(0.0)
+                            .arg(0.0)
+                            .arg(0.0)
+                    }));
+                }
+                bundles
+            }
+            Version::Tuio2 => vec![self.bundle_2(frame.timestamp, &cursors, &blobs)],
+        }
+    }
+
+    /// Collects the cursors and blobs of `frame`, assigning their session IDs.
+    fn shapes(&mut self, frame: &FrameData) -> (Vec<Shape>, Vec<Shape>) {
+        let box_ = &frame.interaction_box;
+        let mut cursors = Vec::new();
+        let mut blobs = Vec::new();
+        for hand in &frame.hands {
+            for finger in &hand.fingers {
+                let pointable = &finger.pointable;
+                if !self.is_cursor(pointable) {
+                    continue;
+                }
+
+                let radius = pointable.width / 2.0;
+                cursors.push(Shape {
+                    session: self.sessions.id(Component::Cursor(pointable.id)),
+                    type_id: Some(finger_type_id(finger.finger_type, hand.is_left)),
+                    position: self.position(box_, pointable.tip_position),
+                    velocity: self.velocity(box_, pointable.tip_velocity),
+                    angle: self.angle(pointable.direction),
+                    size: self.size(box_, radius, radius),
+                    pressure: -pointable.touch_distance,
+                });
+            }
+
+            if self.config.blobs {
+                let half_width = hand.palm_width / 2.0;
+                blobs.push(Shape {
+                    session: self.sessions.id(Component::Blob(hand.id)),
+                    type_id: None,
+                    position: self.position(box_, hand.palm_position),
+                    velocity: self.velocity(box_, hand.palm_velocity),
+                    angle: self.angle(hand.direction),
+                    size: self.size(box_, half_width, half_width),
+                    pressure: 0.0,
+                });
+            }
+        }
+
+        self.sessions.end_frame();
+        (cursors, blobs)
+    }
+
+    fn is_cursor(&self, pointable: &PointableData) -> bool {
+        match self.config.cursors {
+            CursorFilter::All => true,
+            CursorFilter::Extended => pointable.is_extended,
+            CursorFilter::Touching => pointable.touch_zone == TouchZone::Touching,
+        }
+    }
+
+    /// Projects a 3D vector onto the TUIO plane, without normalizing it.
+    fn project(&self, vector: Vector) -> (f32, f32) {
+        match self.config.plane {
+            Plane::Vertical => (vector.x, -vector.y),
+            Plane::Horizontal => (vector.x, vector.z),
+        }
+    }
+
+    fn position(&self, box_: &InteractionBoxData, position: Vector) -> (f32, f32) {
+        let normalized = box_.normalize_point(position, true);
+        match self.config.plane {
+            Plane::Vertical => (normalized.x, 1.0 - normalized.y),
+            Plane::Horizontal => (normalized.x, normalized.z),
+        }
+    }
+
+    /// Converts a velocity in millimeters per second to box sizes per second.
+    fn velocity(&self, box_: &InteractionBoxData, velocity: Vector) -> (f32, f32) {
+        let (x, y) = self.project(velocity);
+        let (width, height) = self.project(box_.size());
+        (x / width, y / height.abs())
+    }
+
+    /// Converts a size in millimeters to box sizes.
+    fn size(&self, box_: &InteractionBoxData, width: f32, height: f32) -> (f32, f32) {
+        let (box_width, box_height) = self.project(box_.size());
+        (2.0 * width / box_width, 2.0 * height / box_height.abs())
+    }
+
+    /// Returns the angle of a direction in the TUIO plane, from 0 to 2π.
+    fn angle(&self, direction: Vector) -> f32 {
+        let (x, y) = self.project(direction);
+        y.atan2(x).rem_euclid(TAU)
+    }
+
+    fn bundle_11(
+        &self,
+        profile: &str,
+        shapes: &[Shape],
+        set: impl Fn(&Shape) -> Message,
+    ) -> Bundle {
+        let mut bundle = Bundle::new(TimeTag::IMMEDIATELY);
+        bundle.messages.push(
+            Message::new(profile)
+                .arg("source")
+                .arg(&*self.config.source),
+        );
+
+        let mut alive = Message::new(profile).arg("alive");
+        for shape in shapes {
+            alive = alive.arg(shape.session);
+        }
+        bundle.messages.push(alive);
+
+        bundle.messages.extend(shapes.iter().map(set));
+        bundle
+            .messages
+            .push(Message::new(profile).arg("fseq").arg(self.frame_id));
+        bundle
+    }
+
+    fn bundle_2(&mut self, timestamp: Timestamp, cursors: &[Shape], blobs: &[Shape]) -> Bundle {
+        let (width, height) = self.config.dimensions;
+        let dimensions = (u32::from(width) << 16 | u32::from(height)) as i32;
+        let mut bundle = Bundle::new(TimeTag::IMMEDIATELY);
+        bundle.messages.push(
+            Message::new("/tuio2/frm")
+                .arg(self.frame_id)
+                .arg(self.frame_time(timestamp))
+                .arg(dimensions)
+                .arg(&*self.config.source),
+        );
+
+        for shape in cursors {
+            let (x, y) = shape.position;
+            let (vx, vy) = shape.velocity;
+            let type_id = shape.type_id.unwrap_or(0);
+            bundle.messages.push(
+                Message::new("/tuio2/ptr")
+                    .arg(shape.session)
+                    .arg(type_id << 16)
+                    .arg(0)
+                    .arg(x)
+                    .arg(y)
+                    .arg(shape.angle)
+                    .arg(0.0)
+                    .arg(shape.size.0 / 2.0)
+                    .arg(shape.pressure)
+                    .arg(vx)
+                    .arg(vy)
+                    .arg(0.0)
+                    .arg(0.0)
+                    .arg(0.0),
+            );
+        }
+        for shape in blobs {
+            let (x, y) = shape.position;
+            let (vx, vy) = shape.velocity;
+            let (w, h) = shape.size;
+            bundle.messages.push(
+                Message::new("/tuio2/bnd")
+                    .arg(shape.session)
+                    .arg(x)
+                    .arg(y)
+                    .arg(shape.angle)
+                    .arg(w)
+                    .arg(h)
+                    .arg(w * h)
+                    .arg(vx)
+                    .arg(vy)
+                    .arg(0.0)
+                    .arg(0.0)
+                    .arg(0.0),
+            );
+        }
+
+        let mut alive = Message::new("/tuio2/alv");
+        for shape in cursors.iter().chain(blobs) {
+            alive = alive.arg(shape.session);
+        }
+        bundle.messages.push(alive);
+        bundle
+    }
+
+    /// Converts a frame timestamp to wall-clock time, relative to the first frame.
+    fn frame_time(&mut self, timestamp: Timestamp) -> TimeTag {
+        let (first, received) = *self
+            .epoch
+            .get_or_insert_with(|| (timestamp, SystemTime::now()));
+        let offset = Duration::from_micros((timestamp.as_raw() - first.as_raw()).max(0) as u64);
+        TimeTag::from_system_time(received + offset)
+    }
+}
+
+impl Listener for Server {
+    fn on_frame(&mut self, controller: &ControllerRef) {
+        let frame = match controller.frame_data() {
+            Some(frame) => frame,
+            None => return,
+        };
+        for bundle in self.bundles(&frame) {
+            osc::send(&self.socket, self.target, &bundle);
+        }
+    }
+}
+
+/// Returns the TUIO 2.0 type ID of a finger.
+fn finger_type_id(finger_type: FingerType, is_left: bool) -> i32 {
+    let id = match finger_type {
+        FingerType::Index => 1,
+        FingerType::Middle => 2,
+        FingerType::Ring => 3,
+        FingerType::Pinky => 4,
+        FingerType::Thumb => 5,
+    };
+    if is_left {
+        id + 5
+    } else {
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::fixtures, osc::Argument};
+
+    fn server(config: ServerConfig) -> Server {
+        Server::new(("127.0.0.1", DEFAULT_PORT), config).unwrap()
+    }
+
+    /// A right hand 50 mm above the center of the interaction box, with 5 extended fingers.
+    fn frame(id: i64) -> FrameData {
+        let hand = fixtures::hand(1, false, Vector::new(0.0, 250.0, 0.0));
+        fixtures::frame(id, vec![hand])
+    }
+
+    fn addresses(bundle: &Bundle) -> Vec<&str> {
+        bundle.messages.iter().map(|m| &*m.address).collect()
+    }
+
+    fn float(arg: &Argument) -> f32 {
+        match arg {
+            Argument::Float(f) => *f,
+            _ => panic!("expected float, got {:?}", arg),
+        }
+    }
+
+    #[test]
+    fn sessions() {
+        let mut sessions = Sessions::default();
+        assert_eq!(sessions.id(Component::Cursor(7)), 1);
+        assert_eq!(sessions.id(Component::Blob(7)), 2);
+        sessions.end_frame();
+
+        assert_eq!(sessions.id(Component::Cursor(8)), 3);
+        assert_eq!(sessions.id(Component::Cursor(7)), 1);
+        sessions.end_frame();
+
+        // Cursor 7 is lost for a frame, and gets a new session when it comes back.
+        assert_eq!(sessions.id(Component::Cursor(8)), 3);
+        sessions.end_frame();
+        assert_eq!(sessions.id(Component::Cursor(7)), 4);
+    }
+
+    #[test]
+    fn tuio11() {
+        let mut server = server(ServerConfig::default());
+        let bundles = server.bundles(&frame(1));
+        assert_eq!(bundles.len(), 2);
+
+        let cursors = &bundles[0];
+        assert_eq!(
+            addresses(cursors),
+            ["/tuio/2Dcur"; 8],
+            "source, alive, 5 times set, fseq"
+        );
+        assert_eq!(
+            cursors.messages[0].args,
+            ["source".into(), "leapcpp".into()]
+        );
+        assert_eq!(
+            cursors.messages[1].args,
+            [
+                "alive".into(),
+                1.into(),
+                2.into(),
+                3.into(),
+                4.into(),
+                5.into()
+            ] as [Argument; 6],
+        );
+        for (set, session) in cursors.messages[2..7].iter().zip(1..) {
+            assert_eq!(set.args.len(), 7);
+            assert_eq!(set.args[..2], ["set".into(), Argument::Int(session)]);
+        }
+        assert_eq!(cursors.messages[7].args, ["fseq".into(), 1.into()]);
+
+        let blobs = &bundles[1];
+        assert_eq!(addresses(blobs), ["/tuio/2Dblb"; 4]);
+        assert_eq!(blobs.messages[1].args, ["alive".into(), 6.into()]);
+        let set = &blobs.messages[2];
+        assert_eq!(set.args.len(), 13);
+        assert_eq!(set.args[..2], ["set".into(), Argument::Int(6)]);
+        // The palm is above the center of the box, so in the upper half of the TUIO plane.
+        assert_eq!((float(&set.args[2]), float(&set.args[3])), (0.5, 0.25));
+        assert_eq!(blobs.messages[3].args, ["fseq".into(), 1.into()]);
+
+        // The next frame keeps the sessions, until the hand is lost.
+        let bundles = server.bundles(&frame(2));
+        assert_eq!(bundles[1].messages[1].args, ["alive".into(), 6.into()]);
+        assert_eq!(bundles[1].messages[3].args, ["fseq".into(), 2.into()]);
+        let bundles = server.bundles(&fixtures::frame(3, Vec::new()));
+        assert_eq!(bundles[0].messages[1].args, ["alive".into()]);
+        assert_eq!(addresses(&bundles[0]).len(), 3);
+        let bundles = server.bundles(&frame(4));
+        assert_eq!(bundles[1].messages[1].args, ["alive".into(), 12.into()]);
+    }
+
+    #[test]
+    fn tuio2() {
+        let mut server = server(ServerConfig {
+            version: Version::Tuio2,
+            plane: Plane::Horizontal,
+            ..ServerConfig::default()
+        });
+        let bundles = server.bundles(&frame(1));
+        assert_eq!(bundles.len(), 1);
+        let bundle = &bundles[0];
+
+        let mut expected = vec!["/tuio2/frm"];
+        expected.extend(["/tuio2/ptr"; 5]);
+        expected.extend(["/tuio2/bnd", "/tuio2/alv"]);
+        assert_eq!(addresses(bundle), expected);
+
+        let frm = &bundle.messages[0].args;
+        assert_eq!(frm.len(), 4);
+        assert_eq!(frm[0], Argument::Int(1));
+        assert!(matches!(frm[1], Argument::Time(_)));
+        assert_eq!(frm[2], Argument::Int(1920 << 16 | 1080));
+        assert_eq!(frm[3], "leapcpp".into());
+
+        // The thumb of a right hand, then the index finger.
+        let thumb = &bundle.messages[1].args;
+        assert_eq!(thumb.len(), 14);
+        assert_eq!(thumb[..3], [1.into(), Argument::Int(5 << 16), 0.into()]);
+        assert_eq!(bundle.messages[2].args[1], Argument::Int(1 << 16));
+        // The fingertips point away from the user, towards the top of the table.
+        assert!(float(&thumb[4]) < 0.5);
+
+        let bnd = &bundle.messages[6].args;
+        assert_eq!(bnd.len(), 12);
+        assert_eq!(bnd[0], Argument::Int(6));
+        assert_eq!((float(&bnd[1]), float(&bnd[2])), (0.5, 0.5));
+
+        assert_eq!(
+            bundle.messages[7].args,
+            (1..=6).map(Argument::Int).collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn empty_interaction_box() {
+        let mut server = server(ServerConfig::default());
+        let mut empty = frame(1);
+        empty.interaction_box.width = 0.0;
+        assert_eq!(server.bundles(&empty), []);
+
+        let bundles = server.bundles(&frame(2));
+        assert_eq!(
+            bundles[0].messages.last().unwrap().args[1],
+            Argument::Int(1)
+        );
+    }
+}
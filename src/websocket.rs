@@ -0,0 +1,705 @@
+//! A WebSocket server speaking the JSON protocol of the original Leap Motion service.
+//!
+//! Older versions of the Leap Motion service served tracking data on `ws://127.0.0.1:6437`, which
+//! is what [leapjs] and other browser-based clients connect to. The Linux SDK 2.3.1 does not
+//! include that server, so [`Server`] provides a replacement that is fed by a [`Controller`]:
+//!
+//! - A client connects to any path (leapjs uses `/v6.json`) and is sent the version handshake of
+//!   protocol version [`PROTOCOL_VERSION`], followed by a `deviceEvent` describing the device.
+//! - Every tracking frame is then sent as a JSON object with the `hands`, `pointables`,
+//!   `gestures` and `interactionBox` of the frame.
+//! - Clients may send the `focused`, `background` and `enableGestures` control messages. A client
+//!   that reports that it is not focused only receives frames if it has also requested
+//!   `background` frames. While at least one client has sent `enableGestures: true`, all
+//!   [`GestureType`]s are enabled on the controller; gestures are only included in the frames sent
+//!   to clients that requested them. Other control messages are ignored.
+//!
+//! The `leap-websocket` binary runs a [`Server`] on the default port.
+//!
+//! [leapjs]: https://github.com/leapmotion/leapjs
+//! [`Controller`]: crate::Controller
+
+use std::{
+    io::{self, ErrorKind},
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc::{self, Receiver, SyncSender, TrySendError},
+        Arc, Mutex, Weak,
+    },
+    thread,
+    time::Duration,
+};
+
+use serde_json::{json, Value};
+use tungstenite::{Message, WebSocket};
+
+use crate::{
+    data::{FrameData, GestureData, GestureDetails, HandData, MotionData, PointableData},
+    device::DeviceType,
+    math::Matrix,
+    pointable::{BoneType, TouchZone},
+    ControllerRef, GestureState, GestureType, Listener,
+};
+
+/// The port the Leap Motion service used for its WebSocket server.
+pub const DEFAULT_PORT: u16 = 6437;
+
+/// The version of the JSON protocol implemented by [`Server`].
+pub const PROTOCOL_VERSION: u32 = 6;
+
+/// The SDK version reported to clients in the version handshake.
+const SERVICE_VERSION: &str = "2.3.1";
+
+/// The number of messages queued for a client before frames are dropped for it.
+const QUEUE_LEN: usize = 16;
+
+/// How long a client thread waits for incoming messages before sending queued ones.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// How long dropping a [`Server`] waits for the connection that wakes up the accept thread.
+const WAKE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A [`Listener`] that forwards the controller's tracking data to WebSocket clients.
+///
+/// Clients are accepted on a background thread once the server is bound; each client is served
+/// by its own thread. Clients that do not keep up with the frame rate miss frames instead of
+/// slowing down the controller. When the [`Server`] is dropped, it stops accepting clients and
+/// the connected clients are disconnected.
+pub struct Server {
+    shared: Arc<Shared>,
+    local_addr: SocketAddr,
+    /// The gestures enabled by the server on behalf of its clients.
+    enabled_gestures: Vec<GestureType>,
+}
+
+struct Shared {
+    clients: Mutex<Vec<SyncSender<Outgoing>>>,
+    /// The most recent `deviceEvent` message, sent to new clients after the handshake.
+    device_event: Mutex<Arc<str>>,
+    /// The number of clients that requested gestures, applied to the controller on the next frame.
+    gesture_clients: AtomicUsize,
+    /// Set when the [`Server`] is dropped, to stop accepting clients.
+    closed: AtomicBool,
+}
+
+enum Outgoing {
+    Frame(Arc<FrameData>),
+    Text(Arc<str>),
+}
+
+impl Server {
+    /// Binds the server to `addr` and starts accepting clients.
+    ///
+    /// To be reachable by leapjs clients, bind to `("127.0.0.1", DEFAULT_PORT)`.
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let shared = Arc::new(Shared {
+            clients: Mutex::new(Vec::new()),
+            device_event: Mutex::new(device_event(false, None).into()),
+            gesture_clients: AtomicUsize::new(0),
+            closed: AtomicBool::new(false),
+        });
+
+        let weak = Arc::downgrade(&shared);
+        thread::Builder::new()
+            .name("leapcpp websocket server".into())
+            .spawn(move || accept(listener, weak))?;
+
+        Ok(Self {
+            shared,
+            local_addr,
+            enabled_gestures: Vec::new(),
+        })
+    }
+
+    /// Returns the address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Returns the number of connected clients.
+    pub fn client_count(&self) -> usize {
+        self.shared.clients.lock().unwrap().len()
+    }
+
+    fn broadcast(&self, make: impl Fn() -> Outgoing) {
+        let mut clients = self.shared.clients.lock().unwrap();
+        clients.retain(|client| match client.try_send(make()) {
+            Ok(()) | Err(TrySendError::Full(_)) => true,
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+    }
+
+    /// Enables the gestures while clients request them, and disables them again afterwards.
+    ///
+    /// Gestures that were already enabled by the application are left alone.
+    fn update_gestures(&mut self, controller: &ControllerRef) {
+        let requested = self.shared.gesture_clients.load(Ordering::Relaxed) > 0;
+        if requested && self.enabled_gestures.is_empty() {
+            for gesture in GestureType::ALL {
+                if !controller.is_gesture_enabled(gesture) {
+                    controller.enable_gesture(gesture);
+                    self.enabled_gestures.push(gesture);
+                }
+            }
+        } else if !requested {
+            for gesture in self.enabled_gestures.drain(..) {
+                controller.disable_gesture(gesture);
+            }
+        }
+    }
+
+    fn update_device(&self, controller: &ControllerRef) {
+        let devices = controller.devices();
+        let device = devices.iter().next();
+        let event: Arc<str> = device_event(
+            controller.is_connected(),
+            device.map(|device| {
+                let device_type = device.device_type().unwrap_or(DeviceType::Peripheral);
+                (device.serial_number(), device_type)
+            }),
+        )
+        .into();
+
+        *self.shared.device_event.lock().unwrap() = event.clone();
+        self.broadcast(|| Outgoing::Text(event.clone()));
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.shared.closed.store(true, Ordering::Relaxed);
+
+        // Wake up the accept thread, which then closes the socket.
+        let mut addr = self.local_addr;
+        if addr.ip().is_unspecified() {
+            addr.set_ip(match addr {
+                SocketAddr::V4(_) => Ipv4Addr::LOCALHOST.into(),
+                SocketAddr::V6(_) => Ipv6Addr::LOCALHOST.into(),
+            });
+        }
+        if let Err(e) = TcpStream::connect_timeout(&addr, WAKE_TIMEOUT) {
+            log::warn!(
+                "failed to stop websocket server on {}: {}",
+                self.local_addr,
+                e
+            );
+        }
+    }
+}
+
+impl Listener for Server {
+    fn on_init(&mut self, controller: &ControllerRef) {
+        self.update_device(controller);
+    }
+
+    fn on_connect(&mut self, controller: &ControllerRef) {
+        self.update_device(controller);
+    }
+
+    fn on_disconnect(&mut self, controller: &ControllerRef) {
+        self.update_device(controller);
+    }
+
+    fn on_device_change(&mut self, controller: &ControllerRef) {
+        self.update_device(controller);
+    }
+
+    fn on_exit(&mut self, controller: &ControllerRef) {
+        for gesture in self.enabled_gestures.drain(..) {
+            controller.disable_gesture(gesture);
+        }
+    }
+
+    fn on_frame(&mut self, controller: &ControllerRef) {
+        self.update_gestures(controller);
+
+        if self.client_count() == 0 {
+            return;
+        }
+
+        let frame = match controller.frame_data() {
+            Some(frame) => Arc::new(frame),
+            None => return,
+        };
+        self.broadcast(|| Outgoing::Frame(frame.clone()));
+    }
+}
+
+fn accept(listener: TcpListener, shared: Weak<Shared>) {
+    for stream in listener.incoming() {
+        match shared.upgrade() {
+            Some(shared) if !shared.closed.load(Ordering::Relaxed) => {}
+            _ => break,
+        }
+
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::warn!("failed to accept websocket client: {}", e);
+                continue;
+            }
+        };
+
+        let shared = shared.clone();
+        let spawned = thread::Builder::new()
+            .name("leapcpp websocket client".into())
+            .spawn(move || {
+                let peer = stream.peer_addr().ok();
+                if let Err(e) = serve(stream, shared) {
+                    log::debug!("websocket client {:?} disconnected: {}", peer, e);
+                }
+            });
+        if let Err(e) = spawned {
+            log::error!("failed to spawn websocket client thread: {}", e);
+        }
+    }
+}
+
+/// The state of a connected client, as set by its control messages.
+struct Client {
+    focused: bool,
+    background: bool,
+    gestures: bool,
+}
+
+impl Client {
+    fn wants_frames(&self) -> bool {
+        self.focused || self.background
+    }
+
+    fn request_gestures(&mut self, enabled: bool, shared: &Weak<Shared>) {
+        if enabled == self.gestures {
+            return;
+        }
+        self.gestures = enabled;
+        if let Some(shared) = shared.upgrade() {
+            if enabled {
+                shared.gesture_clients.fetch_add(1, Ordering::Relaxed);
+            } else {
+                shared.gesture_clients.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn handle_control(&mut self, text: &str, shared: &Weak<Shared>) {
+        let message = match serde_json::from_str::<Value>(text) {
+            Ok(Value::Object(message)) => message,
+            _ => {
+                log::debug!("ignoring invalid websocket message {:?}", text);
+                return;
+            }
+        };
+
+        for (key, value) in message {
+            let enabled = value.as_bool().unwrap_or(false);
+            match &*key {
+                "focused" => self.focused = enabled,
+                "background" => self.background = enabled,
+                "enableGestures" => self.request_gestures(enabled, shared),
+                _ => log::debug!("ignoring websocket control message {:?}", key),
+            }
+        }
+    }
+}
+
+// `tungstenite::Error` is large, but it is only returned once per client.
+#[allow(clippy::result_large_err)]
+fn serve(stream: TcpStream, shared: Weak<Shared>) -> tungstenite::Result<()> {
+    let mut socket = tungstenite::accept(stream).map_err(|e| match e {
+        tungstenite::HandshakeError::Failure(e) => e,
+        tungstenite::HandshakeError::Interrupted(_) => unreachable!("blocking handshake"),
+    })?;
+
+    let (sender, receiver) = mpsc::sync_channel(QUEUE_LEN);
+    {
+        let shared = match shared.upgrade() {
+            Some(shared) => shared,
+            None => return Ok(()),
+        };
+        let version = json!({
+            "version": PROTOCOL_VERSION,
+            "serviceVersion": SERVICE_VERSION,
+        });
+        socket.send(Message::text(version.to_string()))?;
+        let device_event = shared.device_event.lock().unwrap().clone();
+        socket.send(Message::text(&*device_event))?;
+        shared.clients.lock().unwrap().push(sender);
+    }
+
+    socket.get_mut().set_read_timeout(Some(POLL_INTERVAL))?;
+    let mut client = Client {
+        focused: true,
+        background: false,
+        gestures: false,
+    };
+    let result = run_client(&mut socket, &mut client, &receiver, &shared);
+    client.request_gestures(false, &shared);
+    result
+}
+
+#[allow(clippy::result_large_err)]
+fn run_client(
+    socket: &mut WebSocket<TcpStream>,
+    client: &mut Client,
+    receiver: &Receiver<Outgoing>,
+    shared: &Weak<Shared>,
+) -> tungstenite::Result<()> {
+    loop {
+        match socket.read() {
+            Ok(Message::Text(text)) => client.handle_control(&text, shared),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(tungstenite::Error::ConnectionClosed) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+
+        loop {
+            let text = match receiver.try_recv() {
+                Ok(Outgoing::Frame(frame)) if client.wants_frames() => {
+                    frame_json(&frame, client.gestures).to_string()
+                }
+                Ok(Outgoing::Frame(_)) => continue,
+                Ok(Outgoing::Text(text)) => text.to_string(),
+                Err(mpsc::TryRecvError::Empty) => break,
+                // The `Server` was dropped.
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    socket.close(None)?;
+                    return socket.flush();
+                }
+            };
+            socket.send(Message::text(text))?;
+        }
+    }
+}
+
+fn device_event(attached: bool, device: Option<(String, DeviceType)>) -> String {
+    let (id, device_type) = match device {
+        Some((serial, device_type)) => (serial, device_type),
+        None => (String::new(), DeviceType::Peripheral),
+    };
+    let device_type = match device_type {
+        DeviceType::Peripheral => "peripheral",
+        DeviceType::Laptop => "laptop",
+        DeviceType::Keyboard => "keyboard",
+    };
+
+    json!({
+        "event": {
+            "type": "deviceEvent",
+            "state": {
+                "attached": attached,
+                "streaming": attached,
+                "id": id,
+                "type": device_type,
+            },
+        },
+    })
+    .to_string()
+}
+
+fn basis(matrix: &Matrix) -> Value {
+    json!([
+        matrix.x_basis.to_array(),
+        matrix.y_basis.to_array(),
+        matrix.z_basis.to_array(),
+    ])
+}
+
+fn frame_json(frame: &FrameData, gestures: bool) -> Value {
+    let box_ = &frame.interaction_box;
+    let gestures = if gestures {
+        frame.gestures.iter().map(gesture_json).collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut json = json!({
+        "id": frame.id,
+        "timestamp": frame.timestamp.as_raw(),
+        "currentFrameRate": frame.frames_per_second,
+        "hands": frame.hands.iter().map(hand_json).collect::<Vec<_>>(),
+        "pointables": frame
+            .pointables
+            .iter()
+            .map(|pointable| pointable_json(frame, pointable))
+            .collect::<Vec<_>>(),
+        "gestures": gestures,
+        "interactionBox": {
+            "center": box_.center.to_array(),
+            "size": box_.size().to_array(),
+        },
+    });
+    insert_motion(&mut json, frame.motion.as_ref());
+    json
+}
+
+/// Adds the motion factors to a frame or hand object.
+///
+/// Unknown factors are left out instead of being reported as no motion.
+fn insert_motion(json: &mut Value, motion: Option<&MotionData>) {
+    if let (Some(motion), Value::Object(map)) = (motion, json) {
+        map.insert("r".into(), basis(&motion.rotation));
+        map.insert("s".into(), json!(motion.scale_factor));
+        map.insert("t".into(), json!(motion.translation.to_array()));
+    }
+}
+
+fn hand_json(hand: &HandData) -> Value {
+    let mut json = json!({
+        "id": hand.id,
+        "type": if hand.is_left { "left" } else { "right" },
+        "palmPosition": hand.palm_position.to_array(),
+        "stabilizedPalmPosition": hand.stabilized_palm_position.to_array(),
+        "palmVelocity": hand.palm_velocity.to_array(),
+        "palmNormal": hand.palm_normal.to_array(),
+        "palmWidth": hand.palm_width,
+        "direction": hand.direction.to_array(),
+        "sphereCenter": hand.sphere_center.to_array(),
+        "sphereRadius": hand.sphere_radius,
+        "pinchStrength": hand.pinch_strength,
+        "grabStrength": hand.grab_strength,
+        "confidence": hand.confidence,
+        "timeVisible": hand.time_visible.as_secs_f32(),
+        "wrist": hand.wrist_position.to_array(),
+    });
+    if let (Some(arm), Value::Object(map)) = (&hand.arm, &mut json) {
+        map.insert("elbow".into(), json!(arm.elbow_position.to_array()));
+        map.insert("armBasis".into(), basis(&arm.basis));
+        map.insert("armWidth".into(), json!(arm.width));
+    }
+    insert_motion(&mut json, hand.motion.as_ref());
+    json
+}
+
+fn pointable_json(frame: &FrameData, pointable: &PointableData) -> Value {
+    let mut json = json!({
+        "id": pointable.id,
+        "handId": pointable.hand_id.unwrap_or(-1),
+        "tipPosition": pointable.tip_position.to_array(),
+        "stabilizedTipPosition": pointable.stabilized_tip_position.to_array(),
+        "tipVelocity": pointable.tip_velocity.to_array(),
+        "direction": pointable.direction.to_array(),
+        "width": pointable.width,
+        "length": pointable.length,
+        "tool": pointable.is_tool,
+        "extended": pointable.is_extended,
+        "touchZone": match pointable.touch_zone {
+            TouchZone::None => "none",
+            TouchZone::Hovering => "hovering",
+            TouchZone::Touching => "touching",
+        },
+        "touchDistance": pointable.touch_distance,
+        "timeVisible": pointable.time_visible.as_secs_f32(),
+    });
+
+    let finger = pointable
+        .hand_id
+        .and_then(|id| frame.hand(id))
+        .and_then(|hand| {
+            hand.fingers
+                .iter()
+                .find(|finger| finger.pointable.id == pointable.id)
+        });
+    if let (Some(finger), Value::Object(map)) = (finger, &mut json) {
+        let joint = |bone_type| finger.bone(bone_type).prev_joint.to_array();
+        map.insert("type".into(), json!(finger.finger_type as i32));
+        map.insert("carpPosition".into(), json!(joint(BoneType::Metacarpal)));
+        map.insert("mcpPosition".into(), json!(joint(BoneType::Proximal)));
+        map.insert("pipPosition".into(), json!(joint(BoneType::Intermediate)));
+        map.insert("dipPosition".into(), json!(joint(BoneType::Distal)));
+        map.insert(
+            "btipPosition".into(),
+            json!(finger.bone(BoneType::Distal).next_joint.to_array()),
+        );
+        map.insert(
+            "bases".into(),
+            finger.bones.iter().map(|bone| basis(&bone.basis)).collect(),
+        );
+    }
+
+    json
+}
+
+fn gesture_json(gesture: &GestureData) -> Value {
+    let mut json = json!({
+        "id": gesture.id,
+        "type": match gesture.gesture_type() {
+            GestureType::Swipe => "swipe",
+            GestureType::Circle => "circle",
+            GestureType::ScreenTap => "screenTap",
+            GestureType::KeyTap => "keyTap",
+        },
+        "state": match gesture.state {
+            GestureState::Start => "start",
+            GestureState::Update => "update",
+            GestureState::Stop => "stop",
+        },
+        "duration": gesture.duration.as_micros() as u64,
+        "handIds": gesture.hand_ids,
+        "pointableIds": gesture.pointable_ids,
+    });
+
+    let details = match &gesture.details {
+        GestureDetails::Swipe {
+            start_position,
+            position,
+            direction,
+            speed,
+            ..
+        } => json!({
+            "startPosition": start_position.to_array(),
+            "position": position.to_array(),
+            "direction": direction.to_array(),
+            "speed": speed,
+        }),
+        GestureDetails::Circle {
+            center,
+            normal,
+            radius,
+            progress,
+            ..
+        } => json!({
+            "center": center.to_array(),
+            "normal": normal.to_array(),
+            "radius": radius,
+            "progress": progress,
+        }),
+        GestureDetails::ScreenTap {
+            position,
+            direction,
+            progress,
+            ..
+        }
+        | GestureDetails::KeyTap {
+            position,
+            direction,
+            progress,
+            ..
+        } => json!({
+            "position": position.to_array(),
+            "direction": direction.to_array(),
+            "progress": progress,
+        }),
+    };
+
+    if let (Value::Object(map), Value::Object(details)) = (&mut json, details) {
+        map.extend(details);
+    }
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Instant};
+
+    use tungstenite::stream::MaybeTlsStream;
+
+    use super::*;
+    use crate::{
+        data::{fixtures, MotionData},
+        MockController, Vector,
+    };
+
+    type Client = WebSocket<MaybeTlsStream<TcpStream>>;
+
+    fn connect(server: &Server) -> Client {
+        let url = format!("ws://{}/v6.json", server.local_addr());
+        let (mut socket, _) = tungstenite::connect(url).unwrap();
+        if let MaybeTlsStream::Plain(stream) = socket.get_mut() {
+            stream
+                .set_read_timeout(Some(Duration::from_millis(20)))
+                .unwrap();
+        }
+        socket
+    }
+
+    /// Reads the next JSON message, or returns `None` if none arrives before the read timeout.
+    fn read(socket: &mut Client) -> Option<Value> {
+        match socket.read() {
+            Ok(Message::Text(text)) => Some(serde_json::from_str(&text).unwrap()),
+            Ok(message) => panic!("unexpected message {:?}", message),
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+            {
+                None
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Pushes frames until `done` returns `true` for a frame the client receives, and returns that
+    /// frame.
+    fn push_until(
+        mock: &MockController,
+        socket: &mut Client,
+        done: impl Fn(&Value) -> bool,
+    ) -> Value {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        for id in 1.. {
+            assert!(Instant::now() < deadline, "timed out waiting for frame");
+            let mut hand = fixtures::hand(1, true, Vector::new(0.0, 200.0, 0.0));
+            hand.motion = Some(MotionData {
+                translation: Vector::new(1.0, 2.0, 3.0),
+                rotation: Matrix::IDENTITY,
+                scale_factor: 1.5,
+            });
+            mock.push_frame_data(fixtures::frame(id, vec![hand]));
+            mock.wait_idle();
+            while let Some(message) = read(socket) {
+                if message.get("id").is_some() && done(&message) {
+                    return message;
+                }
+            }
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn client() {
+        let mut mock = MockController::new();
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let mut socket = connect(&server);
+        mock.add_listener(server).unwrap();
+
+        let version = read(&mut socket).unwrap();
+        assert_eq!(version["version"], PROTOCOL_VERSION);
+        assert_eq!(version["serviceVersion"], SERVICE_VERSION);
+        let device_event = read(&mut socket).unwrap();
+        assert_eq!(device_event["event"]["type"], "deviceEvent");
+
+        socket
+            .send(Message::text(r#"{"focused":true,"enableGestures":true}"#))
+            .unwrap();
+        push_until(&mock, &mut socket, |_| {
+            mock.is_gesture_enabled(GestureType::Swipe)
+        });
+
+        let frame = push_until(&mock, &mut socket, |_| true);
+        assert!(frame["timestamp"].is_i64());
+        assert!(frame["interactionBox"]["center"].is_array());
+        assert!(frame["interactionBox"]["size"].is_array());
+        assert_eq!(frame["pointables"].as_array().unwrap().len(), 5);
+        assert_eq!(frame["pointables"][0]["handId"], 1);
+        assert_eq!(frame["pointables"][0]["type"], 0);
+        // The frame itself has no motion factors, the hand does.
+        assert!(frame.get("t").is_none());
+        let hand = &frame["hands"][0];
+        assert_eq!(hand["type"], "left");
+        assert_eq!(hand["t"], json!([1.0, 2.0, 3.0]));
+        assert_eq!(hand["s"], 1.5);
+
+        // Gestures are disabled again once the only client that wanted them disconnects.
+        socket.close(None).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while mock.is_gesture_enabled(GestureType::Swipe) {
+            assert!(Instant::now() < deadline, "gestures were not disabled");
+            thread::sleep(Duration::from_millis(10));
+            mock.push_frame_data(fixtures::frame(0, Vec::new()));
+            mock.wait_idle();
+        }
+    }
+}